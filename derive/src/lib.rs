@@ -0,0 +1,177 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Meta, NestedMeta, Result};
+
+/// Where the value read back from the Gpu is inserted.
+enum Target {
+    /// Into `Assets<Self>`, `Info` is `Handle<Self>`.
+    Asset,
+    /// As a component of an entity, `Info` is `Entity`.
+    Component,
+}
+
+/// Derive `GpuInsert` and `FromGpuBytes` for a `#[repr(C)]` struct whose fields are `Pod`.
+///
+/// The staged data is read field by field following the `#[repr(C)]` layout of the struct.
+/// Choose the target of the `insert` with `#[gpu_insert(asset)]` or `#[gpu_insert(component)]`.
+/// The `insert` fails with `GpuInsertError::TargetRemoved` if the asset or entity no longer exists.
+#[proc_macro_derive(GpuInsert, attributes(gpu_insert))]
+pub fn derive_gpu_insert(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    expand(ast).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(ast: DeriveInput) -> Result<TokenStream2> {
+    let target = target(&ast)?;
+
+    if !is_repr_c(&ast) {
+        return Err(Error::new_spanned(
+            &ast.ident,
+            "`GpuInsert` can only be derived for `#[repr(C)]` structs",
+        ));
+    }
+
+    let fields = match &ast.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                &ast.ident,
+                "`GpuInsert` can only be derived for structs",
+            ))
+        }
+    };
+
+    let bevy_gpu_insert = quote!(::bevy_gpu_insert);
+    let private = quote!(#bevy_gpu_insert::__private);
+    let bevy = quote!(#private::bevy);
+
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+
+    let decode = decode(fields, &private);
+
+    let (info, param, insert) = match target {
+        Target::Asset => (
+            quote!(#bevy::asset::Handle<Self>),
+            quote!(#bevy::ecs::system::lifetimeless::SResMut<#bevy::asset::Assets<Self>>),
+            quote!(#private::insert_asset::<Self>(data, info, param)),
+        ),
+        Target::Component => (
+            quote!(#bevy::ecs::entity::Entity),
+            quote! {
+                (
+                    #bevy::ecs::system::Commands<'static, 'static>,
+                    &'static #bevy::ecs::entity::Entities,
+                )
+            },
+            quote!(#private::insert_component::<Self, Self>(data, info, param)),
+        ),
+    };
+
+    Ok(quote! {
+        impl #impl_generics #bevy_gpu_insert::FromGpuBytes for #struct_name #type_generics #where_clause {
+            fn from_gpu_bytes(
+                data: &[u8],
+            ) -> ::core::result::Result<Self, #bevy_gpu_insert::GpuInsertError> {
+                if data.len() != ::core::mem::size_of::<Self>() {
+                    return ::core::result::Result::Err(#bevy_gpu_insert::GpuInsertError::InvalidLength);
+                }
+
+                ::core::result::Result::Ok(#decode)
+            }
+        }
+
+        impl #impl_generics #bevy_gpu_insert::GpuInsert for #struct_name #type_generics #where_clause {
+            type Info = #info;
+            type Param = #param;
+
+            fn insert(
+                data: &#bevy_gpu_insert::GpuInsertData,
+                info: Self::Info,
+                param: &mut #bevy::ecs::system::SystemParamItem<Self::Param>,
+            ) -> ::core::result::Result<(), #bevy_gpu_insert::GpuInsertError> {
+                #insert
+            }
+        }
+    })
+}
+
+/// Read the fields of the struct one after another from `data`.
+fn decode(fields: &Fields, private: &TokenStream2) -> TokenStream2 {
+    let reads = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote!(#private::read_field::<#ty>(data, &mut end))
+    });
+
+    let construct = match fields {
+        Fields::Named(_) => {
+            let names = fields.iter().map(|field| &field.ident);
+            quote!(Self { #(#names: #reads),* })
+        }
+        Fields::Unnamed(_) => quote!(Self(#(#reads),*)),
+        Fields::Unit => return quote!(Self),
+    };
+
+    quote!({
+        let mut end = 0;
+        #construct
+    })
+}
+
+fn target(ast: &DeriveInput) -> Result<Target> {
+    let mut target = None;
+
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("gpu_insert"))
+    {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(Error::new_spanned(meta, "expected `#[gpu_insert(...)]`")),
+        };
+
+        for nested in list.nested.iter() {
+            let parsed = match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("asset") => Target::Asset,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("component") => {
+                    Target::Component
+                }
+                _ => {
+                    return Err(Error::new_spanned(
+                        nested,
+                        "expected `asset` or `component`",
+                    ))
+                }
+            };
+
+            if target.replace(parsed).is_some() {
+                return Err(Error::new_spanned(
+                    nested,
+                    "the target of the `insert` is specified more than once",
+                ));
+            }
+        }
+    }
+
+    target.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "missing `#[gpu_insert(asset)]` or `#[gpu_insert(component)]`",
+        )
+    })
+}
+
+fn is_repr_c(ast: &DeriveInput) -> bool {
+    ast.attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list),
+            _ => None,
+        })
+        .flat_map(|list| list.nested.into_iter())
+        .any(|nested| matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("C")))
+}
@@ -3,14 +3,14 @@ use bevy::{
     prelude::{Commands, Handle, Res, ResMut},
     render::{
         render_resource::{
-            BindGroup, BindGroupDescriptor, BindGroupEntry, Buffer, BufferDescriptor, BufferUsages,
+            BindGroup, BindGroupDescriptor, BindGroupEntry, BufferDescriptor, BufferUsages,
         },
         renderer::RenderDevice,
         Extract,
     },
 };
 
-use bevy_gpu_insert::GpuInsertCommand;
+use bevy_gpu_insert::{GpuAssetInsert, GpuInsertCommand, ReadbackSource};
 
 use crate::{compute::pipeline::GenerateMeshPipeline, generated_mesh::GeneratedMesh};
 
@@ -22,8 +22,7 @@ pub struct GenerateMeshCommand {
 
 #[derive(Clone)]
 pub struct GpuGenerateMeshCommand {
-    pub buffer: Buffer,
-    pub staging_buffer: Buffer,
+    pub buffer: ReadbackSource,
     pub subdivisions: u32,
     pub size: u64,
     pub insert: Handle<GeneratedMesh>,
@@ -59,23 +58,18 @@ pub(crate) fn prepare_generate_mesh_commands(
             * (subdivisions + 1) as u64
             * (subdivisions + 1) as u64;
 
-        let buffer = render_device.create_buffer(&BufferDescriptor {
-            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
-            label: Some("generate mesh buffer"),
-            size,
-            mapped_at_creation: false,
-        });
-
-        let staging_buffer = render_device.create_buffer(&BufferDescriptor {
-            label: Some("staging buffer"),
-            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
-            size,
-            mapped_at_creation: false,
-        });
+        let buffer = ReadbackSource::new(
+            &render_device,
+            &BufferDescriptor {
+                usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+                label: Some("generate mesh buffer"),
+                size,
+                mapped_at_creation: false,
+            },
+        );
 
         gpu_generate_mesh_commands.push(GpuGenerateMeshCommand {
             buffer,
-            staging_buffer,
             subdivisions,
             size,
             insert,
@@ -95,7 +89,7 @@ pub(crate) fn queue_generate_mesh_dispatches(
     render_device: Res<RenderDevice>,
     pipeline: Res<GenerateMeshPipeline>,
     gpu_generate_mesh_commands: Res<Vec<GpuGenerateMeshCommand>>,
-    mut gpu_insert_commands: ResMut<Vec<GpuInsertCommand<GeneratedMesh>>>,
+    mut gpu_insert_commands: ResMut<Vec<GpuInsertCommand<GpuAssetInsert<GeneratedMesh>>>>,
 ) {
     let mut dispatches = Vec::new();
 
@@ -109,13 +103,11 @@ pub(crate) fn queue_generate_mesh_dispatches(
             layout: &pipeline.bind_group_layout,
         });
 
-        gpu_insert_commands.push(GpuInsertCommand {
-            buffer: gpu_command.buffer.clone(),
-            bounds: 0..gpu_command.size,
-            staging_buffer: gpu_command.staging_buffer.clone(),
-            staging_buffer_offset: 0,
-            info: gpu_command.insert.clone_weak(),
-        });
+        gpu_insert_commands.push(GpuInsertCommand::new(
+            gpu_command.buffer.clone(),
+            0..gpu_command.size,
+            gpu_command.insert.clone_weak(),
+        ));
 
         dispatches.push(GenerateMeshDispatch {
             bind_group,
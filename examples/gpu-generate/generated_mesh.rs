@@ -1,10 +1,7 @@
 use bevy::{
-    ecs::system::{
-        lifetimeless::{SRes, SResMut},
-        SystemParamItem,
-    },
+    ecs::system::{lifetimeless::SRes, SystemParamItem},
     pbr::MeshUniform,
-    prelude::{Assets, Commands, Deref, Entity, GlobalTransform, Handle, Mesh, Query, With},
+    prelude::{Commands, Deref, Entity, GlobalTransform, Handle, Mesh, Query, With},
     reflect::TypeUuid,
     render::{
         mesh::{GpuBufferInfo, GpuMesh, Indices},
@@ -14,7 +11,7 @@ use bevy::{
         Extract,
     },
 };
-use bevy_gpu_insert::{GpuInsert, GpuInsertError};
+use bevy_gpu_insert::{FromGpuBytes, GpuInsertError};
 use bevy_into_render_asset::IntoRenderAsset;
 
 // We can't implement `GpuInsert` for `Mesh`. To simplify the rendering of `GeneratedMesh` we prepare it into `Mesh`'s `GpuMesh` with `IntoRenderAsset`.
@@ -22,32 +19,37 @@ use bevy_into_render_asset::IntoRenderAsset;
 #[uuid = "2b6378c3-e473-499f-99b6-7172e6eb0d5a"]
 pub struct GeneratedMesh(pub Mesh);
 
-impl GpuInsert for GeneratedMesh {
-    type Info = Handle<GeneratedMesh>;
-    type Param = SResMut<Assets<GeneratedMesh>>;
+// Matches `Vertex` in `generate_mesh.wgsl`: position, normal and uv.
+type Vertex = [f32; 8];
 
-    fn insert(
-        data: &[u8],
-        info: Self::Info,
-        assets: &mut SystemParamItem<Self::Param>,
-    ) -> Result<(), GpuInsertError> {
+impl Default for GeneratedMesh {
+    // Placeholder to insert the generated mesh into. Its attributes match the generated mesh.
+    fn default() -> Self {
         let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
 
-        let data: Vec<_> = data
-            .chunks_exact(4)
-            .map(|b| f32::from_ne_bytes(b.try_into().unwrap()))
-            .collect();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<[f32; 3]>::new());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, Vec::<[f32; 3]>::new());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, Vec::<[f32; 2]>::new());
+
+        Self(mesh)
+    }
+}
+
+impl FromGpuBytes for GeneratedMesh {
+    fn from_gpu_bytes(data: &[u8]) -> Result<Self, GpuInsertError> {
+        let data: &[Vertex] = bytemuck::try_cast_slice(data)?;
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
 
-        let mut positions = Vec::new();
-        let mut normals = Vec::new();
-        let mut uvs = Vec::new();
+        let mut positions = Vec::with_capacity(data.len());
+        let mut normals = Vec::with_capacity(data.len());
+        let mut uvs = Vec::with_capacity(data.len());
         let mut indices = Vec::new();
 
-        for chunk in data.chunks_exact(8) {
-            let position = [chunk[0], chunk[1], chunk[2]];
-            positions.push(position);
-            normals.push([chunk[3], chunk[4], chunk[5]]);
-            uvs.push([chunk[6], chunk[7]]);
+        for vertex in data {
+            positions.push([vertex[0], vertex[1], vertex[2]]);
+            normals.push([vertex[3], vertex[4], vertex[5]]);
+            uvs.push([vertex[6], vertex[7]]);
         }
 
         let subdivisions = (positions.len() as f32).sqrt() as u32 - 1;
@@ -73,9 +75,7 @@ impl GpuInsert for GeneratedMesh {
         mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
         mesh.set_indices(Some(indices));
 
-        let _ = assets.set(info, Self(mesh));
-
-        Ok(())
+        Ok(Self(mesh))
     }
 }
 
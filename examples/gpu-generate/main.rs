@@ -1,10 +1,10 @@
 use bevy::{
-    asset::{load_internal_asset, HandleId},
+    asset::load_internal_asset,
     prelude::*,
     reflect::TypeUuid,
     render::{render_graph::RenderGraph, Extract, RenderApp, RenderStage},
 };
-use bevy_gpu_insert::{GpuInsertPlugin, StagingNode};
+use bevy_gpu_insert::{GpuAssetInsert, GpuInsertPlugin, StagingNode};
 use bevy_into_render_asset::{IntoRenderAsset, IntoRenderAssetPlugin};
 use bevy_map_handle::MapHandle;
 use compute::{graph::GenerateMeshNode, pipeline::GenerateMeshPipeline};
@@ -54,7 +54,7 @@ impl Plugin for GenerateMeshPlugin {
 
         app.add_asset::<GeneratedMesh>()
             .add_plugin(IntoRenderAssetPlugin::<GeneratedMesh>::default())
-            .add_plugin(GpuInsertPlugin::<GeneratedMesh>::default())
+            .add_plugin(GpuInsertPlugin::<GpuAssetInsert<GeneratedMesh>>::default())
             .add_system_to_stage(CoreStage::First, clear_generate_mesh_commands);
 
         let render_app = app.sub_app_mut(RenderApp);
@@ -67,7 +67,7 @@ impl Plugin for GenerateMeshPlugin {
             .add_system_to_stage(RenderStage::Queue, queue_generate_mesh_dispatches);
 
         let generate_terrain_mesh_node = GenerateMeshNode::default();
-        let staging_node = StagingNode::<GeneratedMesh>::default();
+        let staging_node = StagingNode::<GpuAssetInsert<GeneratedMesh>>::default();
 
         let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
 
@@ -106,13 +106,11 @@ fn setup(
     mut commands: Commands,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut generate_mesh_commands: ResMut<Vec<GenerateMeshCommand>>,
-    generated_meshes: Res<Assets<GeneratedMesh>>,
+    mut generated_meshes: ResMut<Assets<GeneratedMesh>>,
 ) {
     let subdivisions = 20;
 
-    let id = HandleId::random::<GeneratedMesh>();
-    let mut destination = Handle::weak(id);
-    destination.make_strong(&generated_meshes);
+    let destination = generated_meshes.add(GeneratedMesh::default());
     generate_mesh_commands.push(GenerateMeshCommand {
         insert: destination.clone_weak(),
         subdivisions,
@@ -0,0 +1,52 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    asset::Asset,
+    ecs::system::{lifetimeless::SResMut, SystemParamItem},
+    prelude::{Assets, Handle},
+};
+
+use crate::{FromGpuBytes, GpuInsert, GpuInsertData, GpuInsertError};
+
+/// `Insert` an asset `A` [decoded](FromGpuBytes) from staged data to the [`Handle<A>`] passed as `Info`.
+///
+/// The asset is replaced with [`Assets::set`], which sends an [`AssetEvent`](bevy::asset::AssetEvent) once the `insert` completed.
+/// The asset has to exist, add a placeholder before issuing the `insert`.
+/// If the asset was removed before the `insert`, the data is discarded and reported as [`GpuInsertOutcome::Discarded`](crate::GpuInsertOutcome::Discarded).
+pub struct GpuAssetInsert<A>(PhantomData<fn() -> A>);
+
+impl<A> GpuInsert for GpuAssetInsert<A>
+where
+    A: FromGpuBytes + Asset,
+{
+    type Info = Handle<A>;
+    type Param = SResMut<Assets<A>>;
+
+    fn insert(
+        data: &GpuInsertData,
+        info: Self::Info,
+        assets: &mut SystemParamItem<Self::Param>,
+    ) -> Result<(), GpuInsertError> {
+        insert_asset(data, info, assets)
+    }
+}
+
+/// Replace the asset behind `handle` with an asset `A` decoded from `data`.
+#[doc(hidden)]
+pub fn insert_asset<A>(
+    data: &[u8],
+    handle: Handle<A>,
+    assets: &mut Assets<A>,
+) -> Result<(), GpuInsertError>
+where
+    A: FromGpuBytes + Asset,
+{
+    if !assets.contains(&handle) {
+        return Err(GpuInsertError::TargetRemoved);
+    }
+
+    let asset = A::from_gpu_bytes(data)?;
+    let _ = assets.set(handle, asset);
+
+    Ok(())
+}
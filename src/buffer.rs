@@ -0,0 +1,112 @@
+use std::ops::Deref;
+
+use bevy::render::{
+    render_resource::{Buffer, BufferAddress, BufferDescriptor, BufferUsages},
+    renderer::RenderDevice,
+};
+
+/// Buffer data is read back from, keeping the `size` and `usage` it was created with to validate commands reading from it.
+///
+/// Requires [`BufferUsages::COPY_SRC`]. Dereferences to the [`Buffer`], e.g. to bind it.
+#[derive(Clone)]
+pub struct ReadbackSource {
+    buffer: Buffer,
+    size: BufferAddress,
+    usage: BufferUsages,
+}
+
+impl ReadbackSource {
+    pub fn new(render_device: &RenderDevice, descriptor: &BufferDescriptor) -> Self {
+        Self {
+            buffer: render_device.create_buffer(descriptor),
+            size: descriptor.size,
+            usage: descriptor.usage,
+        }
+    }
+
+    /// Track a `buffer` created elsewhere with the given `size` and `usage`.
+    pub fn from_buffer(buffer: Buffer, size: BufferAddress, usage: BufferUsages) -> Self {
+        Self {
+            buffer,
+            size,
+            usage,
+        }
+    }
+
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    pub fn size(&self) -> BufferAddress {
+        self.size
+    }
+
+    pub fn usage(&self) -> BufferUsages {
+        self.usage
+    }
+}
+
+impl Deref for ReadbackSource {
+    type Target = Buffer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+/// Staging buffer owned by the caller, keeping its `size` and `usage` to validate commands staging data in it.
+///
+/// Requires [`BufferUsages::MAP_READ`] and [`BufferUsages::COPY_DST`].
+#[derive(Clone)]
+pub struct StagingBuffer {
+    buffer: Buffer,
+    size: BufferAddress,
+    usage: BufferUsages,
+}
+
+impl StagingBuffer {
+    /// Create a staging buffer of `size` bytes with the required usages.
+    pub fn new(render_device: &RenderDevice, label: Option<&str>, size: BufferAddress) -> Self {
+        let usage = BufferUsages::MAP_READ | BufferUsages::COPY_DST;
+
+        Self {
+            buffer: render_device.create_buffer(&BufferDescriptor {
+                label,
+                usage,
+                size,
+                mapped_at_creation: false,
+            }),
+            size,
+            usage,
+        }
+    }
+
+    /// Track a `buffer` created elsewhere with the given `size` and `usage`.
+    pub fn from_buffer(buffer: Buffer, size: BufferAddress, usage: BufferUsages) -> Self {
+        Self {
+            buffer,
+            size,
+            usage,
+        }
+    }
+
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    pub fn size(&self) -> BufferAddress {
+        self.size
+    }
+
+    pub fn usage(&self) -> BufferUsages {
+        self.usage
+    }
+}
+
+impl Deref for StagingBuffer {
+    type Target = Buffer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
@@ -0,0 +1,86 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::{
+        entity::Entities,
+        system::{Command, SystemParamItem},
+    },
+    prelude::{Commands, Component, Entity, Events, World},
+};
+
+use crate::{FromGpuBytes, GpuInsert, GpuInsertData, GpuInsertError, GpuInsertOutcome};
+
+/// `Insert` a component `C` [decoded](FromGpuBytes) from staged data to the entity passed as `Info`.
+///
+/// If the entity was despawned before the `insert`, the data is discarded and reported as [`GpuInsertOutcome::Discarded`].
+pub struct GpuComponentInsert<C>(PhantomData<fn() -> C>);
+
+impl<C> GpuInsert for GpuComponentInsert<C>
+where
+    C: FromGpuBytes + Component,
+{
+    type Info = Entity;
+    type Param = (Commands<'static, 'static>, &'static Entities);
+
+    fn insert(
+        data: &GpuInsertData,
+        info: Self::Info,
+        param: &mut SystemParamItem<Self::Param>,
+    ) -> Result<(), GpuInsertError> {
+        insert_component::<Self, _>(data, info, param)
+    }
+}
+
+/// Insert a component `C` decoded from `data` to `entity` for the `insert` of `T`.
+#[doc(hidden)]
+pub fn insert_component<T, C>(
+    data: &[u8],
+    entity: Entity,
+    (commands, entities): &mut (Commands, &Entities),
+) -> Result<(), GpuInsertError>
+where
+    T: GpuInsert<Info = Entity>,
+    T: 'static,
+    C: FromGpuBytes + Component,
+{
+    if !entities.contains(entity) {
+        return Err(GpuInsertError::TargetRemoved);
+    }
+
+    let component = C::from_gpu_bytes(data)?;
+
+    commands.add(InsertIfAlive::<T, C> {
+        entity,
+        component,
+        marker: PhantomData,
+    });
+
+    Ok(())
+}
+
+/// Insert `component` unless `entity` was despawned after the `insert` of `T` but before the command was applied.
+struct InsertIfAlive<T, C> {
+    entity: Entity,
+    component: C,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T, C> Command for InsertIfAlive<T, C>
+where
+    T: GpuInsert<Info = Entity>,
+    T: 'static,
+    C: Component,
+{
+    fn write(self, world: &mut World) {
+        match world.get_entity_mut(self.entity) {
+            Some(mut entity) => {
+                entity.insert(self.component);
+            }
+            None => {
+                if let Some(mut events) = world.get_resource_mut::<Events<GpuInsertOutcome<T>>>() {
+                    events.send(GpuInsertOutcome::Discarded(self.entity));
+                }
+            }
+        }
+    }
+}
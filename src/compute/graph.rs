@@ -1,17 +1,30 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, num::NonZeroU32};
+
+use crossbeam_channel::TrySendError;
 
 use bevy::{
     prelude::World,
     render::{
         render_graph,
-        render_resource::{CommandEncoderDescriptor, MapMode},
+        render_resource::{
+            CommandEncoderDescriptor, ImageCopyBuffer, ImageCopyTexture, ImageDataLayout, MapMode,
+            TextureAspect,
+        },
         renderer::{RenderContext, RenderQueue},
     },
 };
 
 use crate::{
-    gpu_insert::{GpuInsertCommand, GpuInsertSender},
-    GpuInsert,
+    diagnostic::GpuInsertPending,
+    frame::GpuInsertFrame,
+    gpu_insert::{AnyGpuInsertCommand, GpuInsertCommand, GpuInsertSender, StagedGpuInsert},
+    memory::{GpuInsertStagingMemory, HeldBackGpuInsertCommands, StagingMemory},
+    registry::GpuBufferRegistry,
+    request::GpuInsertRequest,
+    staging::{StageError, StagingBatch, StagingBufferPool, StagingRange},
+    subscription::GpuReadbackSubscriptions,
+    texture::GpuTextureInsertCommand,
+    GpuInsertError, GpuInsertInfo,
 };
 
 /// `RenderGraph` node staging data-fed `staging_buffers` making them readable by the Cpu.
@@ -25,7 +38,7 @@ impl<T> Default for StagingNode<T> {
 
 impl<T> render_graph::Node for StagingNode<T>
 where
-    T: GpuInsert,
+    T: GpuInsertInfo,
     T: 'static,
 {
     fn run(
@@ -35,39 +48,209 @@ where
         world: &World,
     ) -> Result<(), render_graph::NodeRunError> {
         let gpu_insert_commands = world.resource::<Vec<GpuInsertCommand<T>>>();
+        let gpu_texture_insert_commands = world.resource::<Vec<GpuTextureInsertCommand<T>>>();
+        let staging_buffer_pool = world.resource::<StagingBufferPool>();
         let transfer_sender = world.resource::<GpuInsertSender<T>>();
+        let frame = world.resource::<GpuInsertFrame>().0;
+        let limits = render_context.render_device.limits();
+        let mut staging_batch = StagingBatch::new(staging_buffer_pool);
+
+        let gpu_insert_requests = world.resource::<Vec<GpuInsertRequest<T>>>();
+        let gpu_buffer_registry = world.resource::<GpuBufferRegistry>();
+
+        let requested_commands = gpu_insert_requests.iter().map(|request| {
+            GpuInsertCommand::new(request.buffer, request.bounds.clone(), request.info.clone())
+        });
+
+        let staging_memory = world.resource::<StagingMemory>();
+        let insert_staging_memory = world.resource::<GpuInsertStagingMemory<T>>();
+        let mut held_back = world
+            .resource::<HeldBackGpuInsertCommands<T>>()
+            .commands
+            .lock()
+            .unwrap();
+
+        if let Some(cap) = staging_memory.cap() {
+            staging_buffer_pool.trim(cap);
+        }
+
+        let subscribed_commands = world
+            .resource::<GpuReadbackSubscriptions<T>>()
+            .due(frame)
+            .into_iter()
+            .map(|(command, readback)| (AnyGpuInsertCommand::from(command), Some(readback)));
+
+        // Commands held back in earlier frames are staged first.
+        let mut exceeded_memory = false;
+        let commands: Vec<_> = std::mem::take(&mut *held_back)
+            .into_iter()
+            .chain(
+                gpu_insert_commands
+                    .iter()
+                    .cloned()
+                    .chain(requested_commands)
+                    .map(|command| (AnyGpuInsertCommand::from(command), None)),
+            )
+            .chain(subscribed_commands)
+            .chain(
+                gpu_texture_insert_commands
+                    .iter()
+                    .cloned()
+                    .map(|command| (AnyGpuInsertCommand::from(command), None)),
+            )
+            .filter_map(|(command, readback)| {
+                if let Err(err) = command.validate(&limits, gpu_buffer_registry) {
+                    // Subscriptions issue the command again, only their first failure is reported.
+                    if readback.is_none_or(|readback| readback.failed()) {
+                        let _ = transfer_sender.send(StagedGpuInsert {
+                            command,
+                            result: Err(GpuInsertError::Invalid(err)),
+                            issued_frame: frame,
+                        });
+                    }
+                    return None;
+                }
+
+                // Keep the order of commands once one is held back for memory.
+                let size = command.staged_size(staging_buffer_pool, &render_context.render_device);
+                if exceeded_memory || !staging_memory.fits(size) {
+                    exceeded_memory = true;
+                    held_back.push_back((command, readback));
+                    return None;
+                }
+
+                match command.stage(&mut staging_batch, &render_context.render_device) {
+                    Ok(mut staging) => {
+                        let size = staging.staged_size();
+                        staging.reserve(staging_memory.reserve(insert_staging_memory, size));
+                        if let Some(readback) = readback {
+                            readback.staged();
+                            staging.track(readback);
+                        }
+                        Some((command, staging))
+                    }
+                    // Staged once the earlier commands in its `staging_buffer` were released.
+                    Err(StageError::InUse) => {
+                        held_back.push_back((command, readback));
+                        None
+                    }
+                    Err(StageError::Invalid(err)) => {
+                        if readback.is_none_or(|readback| readback.failed()) {
+                            let _ = transfer_sender.send(StagedGpuInsert {
+                                command,
+                                result: Err(GpuInsertError::Invalid(err)),
+                                issued_frame: frame,
+                            });
+                        }
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        world.resource::<GpuInsertPending<T>>().add(commands.len());
 
         // IMPORTANT! create command queue to submit early. See below.
         let mut encoder = render_context
             .render_device
             .create_command_encoder(&CommandEncoderDescriptor::default());
 
-        for command in gpu_insert_commands.iter() {
-            encoder.copy_buffer_to_buffer(
-                &command.buffer,
-                command.bounds.start,
-                &command.staging_buffer,
-                command.staging_buffer_offset,
-                command.bounds.end - command.bounds.start,
-            );
+        for (command, staging) in commands.iter() {
+            match command {
+                AnyGpuInsertCommand::Buffer(command) => {
+                    let mut staging_offset = staging.range.start;
+
+                    for region in command.regions.iter() {
+                        let buffer = region
+                            .buffer
+                            .resolve(gpu_buffer_registry)
+                            .expect("regions are resolved during validation");
+
+                        encoder.copy_buffer_to_buffer(
+                            buffer,
+                            region.bounds.start,
+                            &staging.buffer,
+                            staging_offset,
+                            region.size(),
+                        );
+
+                        staging_offset += region.size();
+                    }
+                }
+                AnyGpuInsertCommand::Texture(command) => {
+                    let layout = command.layout();
+
+                    encoder.copy_texture_to_buffer(
+                        ImageCopyTexture {
+                            texture: &command.texture,
+                            mip_level: command.mip_level,
+                            origin: command.origin,
+                            aspect: TextureAspect::All,
+                        },
+                        ImageCopyBuffer {
+                            buffer: &staging.buffer,
+                            layout: ImageDataLayout {
+                                offset: staging.range.start,
+                                bytes_per_row: NonZeroU32::new(layout.padded_bytes_per_row()),
+                                rows_per_image: NonZeroU32::new(layout.rows_per_image),
+                            },
+                        },
+                        command.extent,
+                    );
+                }
+            }
         }
 
         // IMPORTANT! Submit commands to the GPU before staging buffer is staged by submitting `map_async` commands on the main command queue.
         let render_queue = world.resource::<RenderQueue>();
         render_queue.submit(std::iter::once(encoder.finish()));
 
-        for command in gpu_insert_commands.iter() {
-            let command_clone = command.clone();
+        // Map every staging buffer once for every command staged in it.
+        let mut mappings: Vec<Vec<(AnyGpuInsertCommand<T>, StagingRange)>> = Vec::new();
+        for (command, staging) in commands {
+            match mappings
+                .iter_mut()
+                .find(|mapping| mapping[0].1.shares_mapping(&staging))
+            {
+                Some(mapping) => mapping.push((command, staging)),
+                None => mappings.push(vec![(command, staging)]),
+            }
+        }
+
+        for mapping in mappings {
             let transfer_sender = transfer_sender.clone();
+            let staging_buffer = mapping[0].1.buffer.clone();
 
-            let buffer_slice = command.staging_buffer.slice(
-                command.staging_buffer_offset
-                    ..command.staging_buffer_offset + (command.bounds.end - command.bounds.start),
-            );
+            let start = mapping.iter().map(|(_, staging)| staging.range.start).min();
+            let end = mapping.iter().map(|(_, staging)| staging.range.end).max();
+            let buffer_slice = staging_buffer.slice(start.unwrap()..end.unwrap());
 
             buffer_slice.map_async(MapMode::Read, move |result| {
-                result.unwrap();
-                transfer_sender.try_send(command_clone).unwrap();
+                let mapped = result.is_ok();
+                if mapped {
+                    mapping[0].1.set_mapped();
+                }
+
+                for (command, staging) in mapping {
+                    let staged = StagedGpuInsert {
+                        command,
+                        result: if mapped {
+                            Ok(staging)
+                        } else {
+                            Err(GpuInsertError::Map)
+                        },
+                        issued_frame: frame,
+                    };
+
+                    // The `MainWorld` is gone, nobody is left to insert the data.
+                    if let Err(TrySendError::Disconnected(staged)) =
+                        transfer_sender.try_send(staged)
+                    {
+                        if let Ok(staging) = staged.result {
+                            staging.release();
+                        }
+                    }
+                }
             });
         }
 
@@ -0,0 +1,211 @@
+use std::{
+    any::TypeId,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics},
+    ecs::system::SystemParam,
+    prelude::{App, Res, ResMut},
+    utils::get_short_name,
+};
+
+use crate::frame::GpuInsertFrame;
+
+/// Base of the [`DiagnosticIds`](DiagnosticId) of every `T`, the low 72 bits are derived from `T` and the measurement.
+const GPU_INSERT_DIAGNOSTICS: u128 = 0x6f1c_3a92_e4d7_4b00_0000_0000_0000_0000;
+
+const MAX_HISTORY_LENGTH: usize = 20;
+
+/// [`DiagnosticIds`](DiagnosticId) of the [`Diagnostics`] registered for `inserts` of `T` by its plugin.
+///
+/// Frames are counted with the [`GpuInsertFrame`](crate::GpuInsertFrame).
+pub struct GpuInsertDiagnostics<T>(PhantomData<fn() -> T>);
+
+impl<T> GpuInsertDiagnostics<T>
+where
+    T: 'static,
+{
+    /// Frames from staging the data to receiving the mapped staging buffer in the `MainWorld`, measured for every command.
+    pub fn map_frames() -> DiagnosticId {
+        diagnostic_id::<T>(0)
+    }
+
+    /// Frames from staging the data to its successful `insert`, measured for every command.
+    pub fn insert_frames() -> DiagnosticId {
+        diagnostic_id::<T>(1)
+    }
+
+    /// Bytes of the staging buffers received in the `MainWorld`, measured every frame.
+    pub fn staged_bytes() -> DiagnosticId {
+        diagnostic_id::<T>(2)
+    }
+
+    /// Commands staged whose staging buffer was not received in the `MainWorld` yet, measured every frame.
+    pub fn pending() -> DiagnosticId {
+        diagnostic_id::<T>(3)
+    }
+
+    /// `Inserts` waiting to be retried in the [`InsertNextFrame<T>`](crate::InsertNextFrame), measured every frame.
+    pub fn backlog() -> DiagnosticId {
+        diagnostic_id::<T>(4)
+    }
+}
+
+fn diagnostic_id<T>(measurement: u8) -> DiagnosticId
+where
+    T: 'static,
+{
+    let mut hasher = DefaultHasher::new();
+    TypeId::of::<T>().hash(&mut hasher);
+
+    DiagnosticId::from_u128(
+        GPU_INSERT_DIAGNOSTICS | (hasher.finish() as u128) << 8 | measurement as u128,
+    )
+}
+
+/// Register the [`Diagnostics`] of `T`, see [`GpuInsertDiagnostics`].
+pub(crate) fn register_gpu_insert_diagnostics<T>(app: &mut App)
+where
+    T: 'static,
+{
+    let name = get_short_name(std::any::type_name::<T>());
+    let mut diagnostics = app.world.get_resource_or_insert_with(Diagnostics::default);
+
+    diagnostics.add(Diagnostic::new(
+        GpuInsertDiagnostics::<T>::map_frames(),
+        format!("{} map frames", name),
+        MAX_HISTORY_LENGTH,
+    ));
+    diagnostics.add(Diagnostic::new(
+        GpuInsertDiagnostics::<T>::insert_frames(),
+        format!("{} insert frames", name),
+        MAX_HISTORY_LENGTH,
+    ));
+    diagnostics.add(
+        Diagnostic::new(
+            GpuInsertDiagnostics::<T>::staged_bytes(),
+            format!("{} staged", name),
+            MAX_HISTORY_LENGTH,
+        )
+        .with_suffix(" bytes"),
+    );
+    diagnostics.add(Diagnostic::new(
+        GpuInsertDiagnostics::<T>::pending(),
+        format!("{} pending", name),
+        MAX_HISTORY_LENGTH,
+    ));
+    diagnostics.add(Diagnostic::new(
+        GpuInsertDiagnostics::<T>::backlog(),
+        format!("{} backlog", name),
+        MAX_HISTORY_LENGTH,
+    ));
+}
+
+/// Commands staged by the [`StagingNode<T>`](crate::StagingNode) whose staging buffer was not received in the `MainWorld` yet, shared by both worlds.
+pub(crate) struct GpuInsertPending<T> {
+    count: Arc<AtomicUsize>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> GpuInsertPending<T> {
+    pub(crate) fn add(&self, count: usize) {
+        self.count.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn sub(&self, count: usize) {
+        self.count.fetch_sub(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn get(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Default for GpuInsertPending<T> {
+    fn default() -> Self {
+        Self {
+            count: Default::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for GpuInsertPending<T> {
+    fn clone(&self) -> Self {
+        Self {
+            count: self.count.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Measurements of one run of the `insert` system of `T`.
+#[derive(Default)]
+pub(crate) struct GpuInsertMeasurements {
+    pub(crate) map_frames: Vec<u64>,
+    pub(crate) insert_frames: Vec<u64>,
+    pub(crate) staged_bytes: u64,
+    /// Staging buffers received, whether they were mapped or not.
+    pub(crate) received: usize,
+}
+
+impl GpuInsertMeasurements {
+    /// Add the measurements to the [`Diagnostics`] of `T` and settle the `received` commands with the `pending` ones.
+    pub(crate) fn record<T>(
+        self,
+        diagnostics: &mut Diagnostics,
+        pending: &GpuInsertPending<T>,
+        backlog: usize,
+    ) where
+        T: 'static,
+    {
+        pending.sub(self.received);
+
+        for frames in self.map_frames {
+            diagnostics.add_measurement(GpuInsertDiagnostics::<T>::map_frames(), frames as f64);
+        }
+        for frames in self.insert_frames {
+            diagnostics.add_measurement(GpuInsertDiagnostics::<T>::insert_frames(), frames as f64);
+        }
+        diagnostics.add_measurement(
+            GpuInsertDiagnostics::<T>::staged_bytes(),
+            self.staged_bytes as f64,
+        );
+        diagnostics.add_measurement(GpuInsertDiagnostics::<T>::pending(), pending.get() as f64);
+        diagnostics.add_measurement(GpuInsertDiagnostics::<T>::backlog(), backlog as f64);
+    }
+}
+
+/// Resources the `insert` system of `T` measures its [`Diagnostics`] with.
+#[derive(SystemParam)]
+pub(crate) struct GpuInsertDiagnosticsParam<'w, 's, T>
+where
+    T: 'static,
+{
+    frame: Res<'w, GpuInsertFrame>,
+    pending: Res<'w, GpuInsertPending<T>>,
+    diagnostics: ResMut<'w, Diagnostics>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+impl<'w, 's, T> GpuInsertDiagnosticsParam<'w, 's, T>
+where
+    T: 'static,
+{
+    pub(crate) fn frame(&self) -> u64 {
+        self.frame.0
+    }
+
+    /// Add the `measurements` of one run of the `insert` system, see [`GpuInsertMeasurements::record`].
+    pub(crate) fn record(&mut self, measurements: GpuInsertMeasurements, backlog: usize) {
+        measurements.record(&mut self.diagnostics, &self.pending, backlog);
+    }
+}
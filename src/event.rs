@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use bevy::{
+    ecs::system::{lifetimeless::SResMut, SystemParamItem},
+    prelude::Events,
+};
+
+use crate::{GpuInsert, GpuInsertData, GpuInsertError};
+
+/// Event carrying data staged on the Gpu together with the `key` of the [`GpuInsertCommand`](crate::GpuInsertCommand) issuing it.
+///
+/// Sent by the [`GpuDataReadyPlugin<K>`](crate::GpuDataReadyPlugin) for every [`GpuInsertCommand<GpuDataReady<K>>`](crate::GpuInsertCommand) whose `info` is the `key`.
+/// Any number of systems can read these events instead of implementing [`GpuInsert`].
+pub struct GpuDataReady<K> {
+    pub key: K,
+    pub data: Arc<[u8]>,
+}
+
+impl<K> Clone for GpuDataReady<K>
+where
+    K: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<K> GpuInsert for GpuDataReady<K>
+where
+    K: Clone + Send + Sync + 'static,
+{
+    type Info = K;
+    type Param = SResMut<Events<GpuDataReady<K>>>;
+
+    fn insert(
+        data: &GpuInsertData,
+        key: Self::Info,
+        events: &mut SystemParamItem<Self::Param>,
+    ) -> Result<(), GpuInsertError> {
+        events.send(GpuDataReady {
+            key,
+            data: Arc::from(data.bytes()),
+        });
+
+        Ok(())
+    }
+}
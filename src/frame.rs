@@ -0,0 +1,19 @@
+use bevy::{
+    prelude::{Commands, Res, World},
+    render::Extract,
+};
+
+/// Frames of the `MainWorld` counted by the crate, to measure how many frames pass between staging data and its `insert`.
+///
+/// Incremented at the start of every frame and extracted to the `RenderWorld`, where it is the frame whose data is rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GpuInsertFrame(pub u64);
+
+pub(crate) fn count_frames(world: &mut World) {
+    let mut frame = world.resource_mut::<GpuInsertFrame>();
+    frame.0 = frame.0.wrapping_add(1);
+}
+
+pub(crate) fn extract_frame(mut commands: Commands, frame: Extract<Res<GpuInsertFrame>>) {
+    commands.insert_resource(**frame);
+}
@@ -0,0 +1,8 @@
+use crate::GpuInsertError;
+
+/// Decode a value from data staged on the Gpu.
+///
+/// Used by built-in `inserts` like [`GpuAssetInsert`](crate::GpuAssetInsert) and [`GpuComponentInsert`](crate::GpuComponentInsert), which only need to know how to decode the value they insert.
+pub trait FromGpuBytes: Sized {
+    fn from_gpu_bytes(data: &[u8]) -> Result<Self, GpuInsertError>;
+}
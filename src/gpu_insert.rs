@@ -1,26 +1,56 @@
-use std::ops::Range;
+use std::{borrow::Cow, fmt, marker::PhantomData, ops::Range};
 
 use bevy::{
+    core::Pod,
+    diagnostic::Diagnostics,
     ecs::system::{StaticSystemParam, SystemParam, SystemParamItem},
-    prelude::{Commands, Res, ResMut},
-    render::render_resource::{Buffer, BufferAddress},
+    log::error,
+    prelude::{Commands, EventWriter, Events, Mut, Res, ResMut, World},
+    render::{
+        render_resource::{BufferAddress, BufferUsages, BufferVec, WgpuLimits},
+        renderer::RenderDevice,
+    },
 };
 use crossbeam_channel::{Receiver, Sender};
 use std::ops::Deref;
 
-/// Sender in the `RenderWorld` for [`GpuInsertCommands`](GpuInsertCommand) after the `staging_buffer` was staged (readable).
-pub struct GpuInsertSender<T>(pub Sender<GpuInsertCommand<T>>)
+use crate::{
+    buffer::{ReadbackSource, StagingBuffer},
+    diagnostic::{GpuInsertDiagnosticsParam, GpuInsertMeasurements, GpuInsertPending},
+    frame::GpuInsertFrame,
+    registry::{GpuBufferId, GpuBufferRegistry},
+    staging::{StageError, StagingBatch, StagingBufferPool, StagingRange},
+    texture::{GpuTextureInsertCommand, GpuTextureLayout},
+    validation::{
+        validate_gpu_insert_command, validate_gpu_texture_insert_command, GpuInsertValidationError,
+    },
+};
+
+/// Sender in the `RenderWorld` for [`GpuInsertCommands`](GpuInsertCommand) after the `staging_buffer` was staged (readable) or staging failed.
+pub struct GpuInsertSender<T>(pub Sender<StagedGpuInsert<T>>)
+where
+    T: GpuInsertInfo;
+
+/// Receiver in the `MainWorld` of [`GpuInsertCommands`](GpuInsertCommand) after the `staging_buffer` was staged (readable) or staging failed.
+pub struct GpuInsertReceiver<T>(pub Receiver<StagedGpuInsert<T>>)
 where
-    T: GpuInsert;
+    T: GpuInsertInfo;
 
-/// Receiver in the `MainWorld` of [`GpuInsertCommands`](GpuInsertCommand) after the `staging_buffer` was staged (readable).
-pub struct GpuInsertReceiver<T>(pub Receiver<GpuInsertCommand<T>>)
+/// Command whose `staging_buffer` was staged (readable), or the error why staging failed.
+pub struct StagedGpuInsert<T>
 where
-    T: GpuInsert;
+    T: GpuInsertInfo,
+{
+    pub command: AnyGpuInsertCommand<T>,
+    /// Mapped range the data is staged in.
+    pub result: Result<StagingRange, GpuInsertError>,
+    /// [`GpuInsertFrame`] the data was staged in.
+    pub issued_frame: u64,
+}
 
 impl<T> Clone for GpuInsertSender<T>
 where
-    T: GpuInsert,
+    T: GpuInsertInfo,
 {
     fn clone(&self) -> Self {
         Self(self.0.clone())
@@ -29,9 +59,9 @@ where
 
 impl<T> Deref for GpuInsertSender<T>
 where
-    T: GpuInsert,
+    T: GpuInsertInfo,
 {
-    type Target = Sender<GpuInsertCommand<T>>;
+    type Target = Sender<StagedGpuInsert<T>>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -40,9 +70,9 @@ where
 
 impl<T> Deref for GpuInsertReceiver<T>
 where
-    T: GpuInsert,
+    T: GpuInsertInfo,
 {
-    type Target = Receiver<GpuInsertCommand<T>>;
+    type Target = Receiver<StagedGpuInsert<T>>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -51,36 +81,102 @@ where
 
 pub(crate) fn create_transfer_channels<T>() -> (GpuInsertSender<T>, GpuInsertReceiver<T>)
 where
-    T: GpuInsert,
+    T: GpuInsertInfo,
 {
     let (s, r) = crossbeam_channel::unbounded();
     (GpuInsertSender(s), GpuInsertReceiver(r))
 }
 
-/// Issue an [`insert`](GpuInsert::insert) with data from `buffer` copied to `staging_buffer`  to be staged (readable) for the `MainWorld`.
+/// Issue an [`insert`](GpuInsert::insert) with data from the `regions` copied to a staging buffer to be staged (readable) for the `MainWorld`.
 ///
-/// Data from `buffer` within the `bounds` will be copied to the `staging_buffer` starting at the `staging_buffer_offset`.
+/// Data from every region is copied to the staging buffer back to back.
+/// The staging buffer is taken from the [`StagingBufferPool`](crate::StagingBufferPool) unless a `staging_buffer` is given, then the data is copied to it starting at the `staging_buffer_offset`.
+/// The `insert` receives the data of every region together with the `info`, see [`GpuInsertData`].
 ///
-/// Dispatched by pushing a [`GpuInsertCommand`] for `T` implementing [`GpuInsert`] to the resource [`Vec<GpuInsertCommand<T>>`] in the `RenderWorld`.
+/// Dispatched by pushing a [`GpuInsertCommand`] for `T` implementing [`GpuInsert`], or [`ExclusiveGpuInsert<T>`] for `T` implementing [`GpuInsertExclusive`], to the resource [`Vec<GpuInsertCommand<T>>`] in the `RenderWorld`.
 pub struct GpuInsertCommand<T>
 where
-    T: GpuInsert,
+    T: GpuInsertInfo,
 {
-    pub buffer: Buffer,
-    pub bounds: Range<BufferAddress>,
-    pub staging_buffer: Buffer,
+    pub regions: Vec<GpuInsertRegion>,
+    pub staging_buffer: Option<StagingBuffer>,
     pub staging_buffer_offset: BufferAddress,
     pub info: T::Info,
 }
 
+impl<T> GpuInsertCommand<T>
+where
+    T: GpuInsertInfo,
+{
+    /// Command copying data from `buffer` within the `bounds` as its only region, staged in a pooled staging buffer.
+    pub fn new(
+        buffer: impl Into<GpuInsertSource>,
+        bounds: Range<BufferAddress>,
+        info: T::Info,
+    ) -> Self {
+        Self {
+            regions: vec![GpuInsertRegion {
+                buffer: buffer.into(),
+                bounds,
+            }],
+            staging_buffer: None,
+            staging_buffer_offset: 0,
+            info,
+        }
+    }
+
+    /// Command copying the `elements` of a [`BufferVec`], or `None` if no buffer was reserved yet.
+    ///
+    /// The `usage` has to be the usages the [`BufferVec`] was created with, which need to include [`BufferUsages::COPY_SRC`].
+    /// Decode the elements e.g. with `GpuInsertPod` behind the `bytemuck` feature.
+    pub fn from_buffer_vec<E>(
+        buffer_vec: &BufferVec<E>,
+        usage: BufferUsages,
+        elements: Range<usize>,
+        info: T::Info,
+    ) -> Option<Self>
+    where
+        E: Pod,
+    {
+        let element_size = std::mem::size_of::<E>() as BufferAddress;
+        let buffer = ReadbackSource::from_buffer(
+            buffer_vec.buffer()?.clone(),
+            buffer_vec.capacity() as BufferAddress * element_size,
+            usage | BufferUsages::COPY_DST,
+        );
+
+        Some(Self::new(
+            buffer,
+            elements.start as BufferAddress * element_size
+                ..elements.end as BufferAddress * element_size,
+            info,
+        ))
+    }
+
+    /// Stage the data in `staging_buffer` starting at the `staging_buffer_offset` instead of a pooled staging buffer.
+    pub fn with_staging_buffer(
+        mut self,
+        staging_buffer: StagingBuffer,
+        staging_buffer_offset: BufferAddress,
+    ) -> Self {
+        self.staging_buffer = Some(staging_buffer);
+        self.staging_buffer_offset = staging_buffer_offset;
+        self
+    }
+
+    /// Size of the data of every region.
+    pub fn size(&self) -> BufferAddress {
+        self.regions.iter().map(GpuInsertRegion::size).sum()
+    }
+}
+
 impl<T> Clone for GpuInsertCommand<T>
 where
-    T: GpuInsert,
+    T: GpuInsertInfo,
 {
     fn clone(&self) -> Self {
         Self {
-            buffer: self.buffer.clone(),
-            bounds: self.bounds.clone(),
+            regions: self.regions.clone(),
             staging_buffer: self.staging_buffer.clone(),
             staging_buffer_offset: self.staging_buffer_offset,
             info: self.info.clone(),
@@ -88,38 +184,456 @@ where
     }
 }
 
+/// Data from `buffer` within the `bounds` to be copied by a [`GpuInsertCommand`].
+#[derive(Clone)]
+pub struct GpuInsertRegion {
+    pub buffer: GpuInsertSource,
+    pub bounds: Range<BufferAddress>,
+}
+
+/// Buffer data is copied from, either given directly or named by its [`GpuBufferId`].
+///
+/// Ids are resolved in the [`GpuBufferRegistry`] when the [`StagingNode`](crate::StagingNode) runs.
+/// Commands naming a buffer missing from the registry are rejected with [`GpuInsertValidationError::MissingBuffer`].
+#[derive(Clone)]
+pub enum GpuInsertSource {
+    Buffer(ReadbackSource),
+    Id(GpuBufferId),
+}
+
+impl GpuInsertSource {
+    /// The buffer given directly or registered as the id.
+    pub fn resolve<'a>(
+        &'a self,
+        registry: &'a GpuBufferRegistry,
+    ) -> Result<&'a ReadbackSource, GpuInsertValidationError> {
+        match self {
+            GpuInsertSource::Buffer(buffer) => Ok(buffer),
+            GpuInsertSource::Id(id) => registry
+                .get(*id)
+                .ok_or(GpuInsertValidationError::MissingBuffer(*id)),
+        }
+    }
+}
+
+impl From<ReadbackSource> for GpuInsertSource {
+    fn from(buffer: ReadbackSource) -> Self {
+        GpuInsertSource::Buffer(buffer)
+    }
+}
+
+impl From<GpuBufferId> for GpuInsertSource {
+    fn from(id: GpuBufferId) -> Self {
+        GpuInsertSource::Id(id)
+    }
+}
+
+impl GpuInsertRegion {
+    /// Size of the data within the `bounds`.
+    pub fn size(&self) -> BufferAddress {
+        self.bounds.end - self.bounds.start
+    }
+}
+
+/// Any command issuing an `insert` for `T`, passed through staging, retries and [`GpuInsertDeadLetters<T>`].
+pub enum AnyGpuInsertCommand<T>
+where
+    T: GpuInsertInfo,
+{
+    Buffer(GpuInsertCommand<T>),
+    Texture(GpuTextureInsertCommand<T>),
+}
+
+impl<T> AnyGpuInsertCommand<T>
+where
+    T: GpuInsertInfo,
+{
+    pub fn info(&self) -> &T::Info {
+        match self {
+            AnyGpuInsertCommand::Buffer(command) => &command.info,
+            AnyGpuInsertCommand::Texture(command) => &command.info,
+        }
+    }
+
+    /// Validate the command before anything is copied.
+    pub(crate) fn validate(
+        &self,
+        limits: &WgpuLimits,
+        registry: &GpuBufferRegistry,
+    ) -> Result<(), GpuInsertValidationError> {
+        match self {
+            AnyGpuInsertCommand::Buffer(command) => {
+                validate_gpu_insert_command(command, limits, registry)
+            }
+            AnyGpuInsertCommand::Texture(command) => {
+                validate_gpu_texture_insert_command(command, limits)
+            }
+        }
+    }
+
+    /// Bytes copied to the staging buffer.
+    pub(crate) fn staging_size(&self) -> BufferAddress {
+        match self {
+            AnyGpuInsertCommand::Buffer(command) => command.size(),
+            AnyGpuInsertCommand::Texture(command) => command.staging_size(),
+        }
+    }
+
+    /// The `staging_buffer` of the command and the offset to stage the data at, if given.
+    fn staging_buffer(&self) -> (Option<&StagingBuffer>, BufferAddress) {
+        match self {
+            AnyGpuInsertCommand::Buffer(command) => (
+                command.staging_buffer.as_ref(),
+                command.staging_buffer_offset,
+            ),
+            AnyGpuInsertCommand::Texture(command) => (
+                command.staging_buffer.as_ref(),
+                command.staging_buffer_offset,
+            ),
+        }
+    }
+
+    /// Bytes of staging memory the command takes up once staged, the whole staging buffer if it is taken from the `pool`.
+    pub(crate) fn staged_size(
+        &self,
+        pool: &StagingBufferPool,
+        render_device: &RenderDevice,
+    ) -> BufferAddress {
+        match self.staging_buffer() {
+            (Some(_), _) => self.staging_size(),
+            (None, _) => pool.buffer_size(render_device, self.staging_size()),
+        }
+    }
+
+    /// Range to stage the data in, within the `staging_buffer` of the command or a pooled staging buffer.
+    pub(crate) fn stage(
+        &self,
+        staging_batch: &mut StagingBatch,
+        render_device: &RenderDevice,
+    ) -> Result<StagingRange, StageError> {
+        let (staging_buffer, staging_buffer_offset) = self.staging_buffer();
+
+        staging_batch.stage(
+            render_device,
+            staging_buffer,
+            staging_buffer_offset,
+            self.staging_size(),
+        )
+    }
+
+    /// Strip the padding of texture rows from `staged`, the mapped [`StagingRange`].
+    fn pack<'a>(&self, staged: &'a [u8]) -> Cow<'a, [u8]> {
+        match self {
+            AnyGpuInsertCommand::Buffer(_) => Cow::Borrowed(staged),
+            AnyGpuInsertCommand::Texture(command) => command.layout().pack(staged),
+        }
+    }
+
+    /// Data passed to the `insert` from the `packed` staged data.
+    fn data<'a>(&'a self, packed: &'a [u8], meta: GpuInsertMeta) -> GpuInsertData<'a> {
+        match self {
+            AnyGpuInsertCommand::Buffer(command) => GpuInsertData {
+                data: packed,
+                regions: &command.regions,
+                texture: None,
+                meta,
+            },
+            AnyGpuInsertCommand::Texture(command) => GpuInsertData {
+                data: packed,
+                regions: &[],
+                texture: Some(command.layout()),
+                meta,
+            },
+        }
+    }
+}
+
+impl<T> Clone for AnyGpuInsertCommand<T>
+where
+    T: GpuInsertInfo,
+{
+    fn clone(&self) -> Self {
+        match self {
+            AnyGpuInsertCommand::Buffer(command) => AnyGpuInsertCommand::Buffer(command.clone()),
+            AnyGpuInsertCommand::Texture(command) => AnyGpuInsertCommand::Texture(command.clone()),
+        }
+    }
+}
+
+impl<T> From<GpuInsertCommand<T>> for AnyGpuInsertCommand<T>
+where
+    T: GpuInsertInfo,
+{
+    fn from(command: GpuInsertCommand<T>) -> Self {
+        AnyGpuInsertCommand::Buffer(command)
+    }
+}
+
+impl<T> From<GpuTextureInsertCommand<T>> for AnyGpuInsertCommand<T>
+where
+    T: GpuInsertInfo,
+{
+    fn from(command: GpuTextureInsertCommand<T>) -> Self {
+        AnyGpuInsertCommand::Texture(command)
+    }
+}
+
+/// Staged (readable) data of a [`GpuInsertCommand`] or [`GpuTextureInsertCommand`] passed to its `insert`.
+///
+/// Dereferences to the data of every region back to back, or to the tightly packed rows of a texture.
+/// Use [`GpuInsertData::regions`] to read the regions one by one.
+#[derive(Clone, Copy)]
+pub struct GpuInsertData<'a> {
+    data: &'a [u8],
+    regions: &'a [GpuInsertRegion],
+    texture: Option<GpuTextureLayout>,
+    meta: GpuInsertMeta,
+}
+
+impl<'a> GpuInsertData<'a> {
+    /// Data of every region back to back, or the tightly packed rows of a texture.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// When the data was staged and how often inserting it was attempted.
+    pub fn meta(&self) -> GpuInsertMeta {
+        self.meta
+    }
+
+    /// Format and dimensions of the tightly packed rows if the data was staged by a [`GpuTextureInsertCommand`].
+    pub fn texture(&self) -> Option<GpuTextureLayout> {
+        self.texture
+    }
+
+    /// Data of every region in the order of the `regions` of the [`GpuInsertCommand`]. Yields nothing for textures.
+    pub fn regions(&self) -> impl Iterator<Item = &'a [u8]> + 'a {
+        let data = self.data;
+
+        self.regions.iter().scan(0, move |start, region| {
+            let end = *start + region.size() as usize;
+            let region = &data[*start..end];
+            *start = end;
+            Some(region)
+        })
+    }
+
+    /// Data of the region at `index`.
+    pub fn region(&self, index: usize) -> Option<&'a [u8]> {
+        self.regions().nth(index)
+    }
+}
+
+impl Deref for GpuInsertData<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.data
+    }
+}
+
+/// When and how the data passed to an `insert` was staged, e.g. to compensate the latency of picking results or simulation state read back from the Gpu.
+///
+/// Frames are counted by the [`GpuInsertFrame`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GpuInsertMeta {
+    /// Frame the data was staged in by the [`StagingNode`](crate::StagingNode).
+    pub issued_frame: u64,
+    /// Frame the mapped staging buffer was received in the `MainWorld`.
+    pub completed_frame: u64,
+    /// Bytes staged, including the padding of texture rows.
+    pub byte_length: u64,
+    /// Failed attempts to `insert` the data before, `0` on the first attempt.
+    pub attempts: u32,
+}
+
+/// Why an `insert` did not complete, deciding what happens to its [`GpuInsertCommand`].
+#[derive(Debug)]
 pub enum GpuInsertError {
+    /// Retry the `insert` next frame.
     RetryNextUpdate,
+    /// Retry the `insert` after the given number of frames.
+    RetryAfter(u32),
+    /// Discard the data without inserting it.
+    Discard,
+    /// The target of the `insert`, e.g. an entity or an asset, was removed before the data was staged. The data is discarded.
+    TargetRemoved,
+    /// The staged data is not aligned to the type it should be read as.
+    Misaligned,
+    /// The length of the staged data is not a multiple of the size of the type it should be read as.
+    InvalidLength,
+    /// The `insert` failed permanently for the given reason.
+    Failed(Cow<'static, str>),
+    /// Mapping the `staging_buffer` failed, e.g. because the device was lost. Re-dispatch the work on the Gpu to recover.
+    Map,
+    /// The `insert` was given up after the given number of attempts exceeded the [`GpuInsertRetryPolicy`].
+    RetriesExceeded(u32),
+    /// The command was rejected by the [`StagingNode`](crate::StagingNode) before anything was copied.
+    Invalid(GpuInsertValidationError),
+}
+
+impl fmt::Display for GpuInsertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuInsertError::RetryNextUpdate => write!(f, "retry next update"),
+            GpuInsertError::RetryAfter(frames) => write!(f, "retry after {} frames", frames),
+            GpuInsertError::Discard => write!(f, "discarded"),
+            GpuInsertError::TargetRemoved => write!(f, "the target of the insert was removed"),
+            GpuInsertError::Misaligned => write!(f, "the staged data is misaligned"),
+            GpuInsertError::InvalidLength => write!(f, "the staged data has an invalid length"),
+            GpuInsertError::Failed(reason) => write!(f, "failed: {}", reason),
+            GpuInsertError::Map => write!(f, "failed to map the staging buffer"),
+            GpuInsertError::RetriesExceeded(attempts) => {
+                write!(f, "gave up after {} attempts", attempts)
+            }
+            GpuInsertError::Invalid(err) => write!(f, "invalid command: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for GpuInsertError {}
+
+/// Event sent by the [`GpuInsertPlugin<T>`](crate::GpuInsertPlugin) or [`GpuInsertExclusivePlugin<T>`](crate::GpuInsertExclusivePlugin) for every attempt to conclude a [`GpuInsertCommand<T>`](GpuInsertCommand).
+pub enum GpuInsertOutcome<T>
+where
+    T: GpuInsertInfo,
+{
+    /// The data was inserted.
+    /// Inserts deferred with [`Commands`], like the [`GpuComponentInsert`](crate::GpuComponentInsert), may still be discarded when applied.
+    Inserted(T::Info),
+    /// The data was discarded on request of the `insert` or because its target was removed.
+    Discarded(T::Info),
+    /// The `insert` failed permanently for the given reason.
+    Failed(T::Info, GpuInsertError),
+    /// The `insert` will be retried in a later frame.
+    Retried(T::Info),
+}
+
+/// Data passed forth from a [`GpuInsertCommand`] to the `insert` it issues.
+///
+/// Shared by every kind of `insert`, implemented for every [`GpuInsert`] and every [`ExclusiveGpuInsert`].
+pub trait GpuInsertInfo {
+    type Info: Clone + Send + Sync + 'static;
+}
+
+impl<T> GpuInsertInfo for T
+where
+    T: GpuInsert,
+{
+    type Info = <T as GpuInsert>::Info;
 }
 
 /// `Insert` data to the `MainWorld` from staged (readable) buffers on the Gpu.
 pub trait GpuInsert {
     /// Data required to complete the `insert`.
     /// It will be passed forth from the [`GpuInsertCommand`] issuing this `insert` to [`GpuInsert::insert`].
-    type Info: Clone + Send + Sync;
+    type Info: Clone + Send + Sync + 'static;
     /// Access ECS data required to complete the `insert` within [`GpuInsert::insert`].
     /// Use [`lifetimeless`](bevy::ecs::system::lifetimeless) [`SystemParam`] for convenience.
     type Param: SystemParam;
 
     /// Insert data into the `MainWorld`.
     fn insert(
-        data: &[u8],
+        data: &GpuInsertData,
         info: Self::Info,
         param: &mut SystemParamItem<Self::Param>,
     ) -> Result<(), GpuInsertError>;
+
+    /// Insert data of every [`GpuInsertCommand`] concluded this frame at once, e.g. to borrow from `param` once and preallocate.
+    ///
+    /// Returns the result of every item in order. Calls [`GpuInsert::insert`] for every item by default.
+    fn insert_batch<'a>(
+        items: impl Iterator<Item = (GpuInsertData<'a>, Self::Info)>,
+        param: &mut SystemParamItem<Self::Param>,
+    ) -> Vec<Result<(), GpuInsertError>> {
+        items
+            .map(|(data, info)| Self::insert(&data, info, param))
+            .collect()
+    }
+}
+
+/// How often and how fast failed `inserts` are retried.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GpuInsertRetryPolicy {
+    /// Number of attempts after which an `insert` is given up. Retried indefinitely if `None`.
+    pub max_attempts: Option<u32>,
+    /// Double the frames between attempts, waiting at most the given number of frames. Retried as requested if `None`.
+    pub max_backoff: Option<u32>,
+}
+
+impl GpuInsertRetryPolicy {
+    /// Frames to wait before the next attempt after `attempts` failed ones.
+    fn backoff(&self, attempts: u32) -> u32 {
+        self.max_backoff.map_or(0, |max_backoff| {
+            2u32.saturating_pow(attempts.saturating_sub(1))
+                .saturating_sub(1)
+                .min(max_backoff)
+        })
+    }
+
+    /// Whether an `insert` is given up after `attempts` failed ones.
+    fn exceeded(&self, attempts: u32) -> bool {
+        self.max_attempts
+            .is_some_and(|max_attempts| attempts >= max_attempts)
+    }
 }
 
 /// Failed `inserts` to be scheduled for the next frame.
 pub struct InsertNextFrame<T>
 where
-    T: GpuInsert,
+    T: GpuInsertInfo,
 {
-    pub commands: Vec<GpuInsertCommand<T>>,
+    pub commands: Vec<RetryGpuInsert<T>>,
+    pub retry_policy: GpuInsertRetryPolicy,
+}
+
+/// [`GpuInsertCommand`] whose `insert` will be retried once `delay` frames passed.
+pub struct RetryGpuInsert<T>
+where
+    T: GpuInsertInfo,
+{
+    pub command: AnyGpuInsertCommand<T>,
+    /// Mapped range the data is staged in.
+    pub staging: StagingRange,
+    pub delay: u32,
+    /// Metadata passed to the `insert`, counting the failed `attempts`.
+    pub meta: GpuInsertMeta,
+}
+
+impl<T> InsertNextFrame<T>
+where
+    T: GpuInsertInfo,
+{
+    pub fn new(retry_policy: GpuInsertRetryPolicy) -> Self {
+        Self {
+            commands: Default::default(),
+            retry_policy,
+        }
+    }
 }
 
 impl<T> Default for InsertNextFrame<T>
 where
-    T: GpuInsert,
+    T: GpuInsertInfo,
+{
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+/// `Inserts` given up after exceeding the [`GpuInsertRetryPolicy`] or because their staging buffer could not be mapped. Their staging buffers are unmapped.
+///
+/// Drain the `commands` to re-dispatch them.
+pub struct GpuInsertDeadLetters<T>
+where
+    T: GpuInsertInfo,
+{
+    pub commands: Vec<AnyGpuInsertCommand<T>>,
+}
+
+impl<T> Default for GpuInsertDeadLetters<T>
+where
+    T: GpuInsertInfo,
 {
     fn default() -> Self {
         Self {
@@ -128,53 +642,302 @@ where
     }
 }
 
-/// Clear completed [`GpuInsertCommands`](GpuInsertCommand).
+/// `Insert` data to the `MainWorld` from staged (readable) buffers on the Gpu with exclusive access to the `World`.
+///
+/// Driven by an exclusive system added by the [`GpuInsertExclusivePlugin<T>`](crate::GpuInsertExclusivePlugin) for heavy structural `inserts`, e.g. spawning scenes from data generated on the Gpu.
+/// Issue these `inserts` with [`GpuInsertCommands<ExclusiveGpuInsert<T>>`](GpuInsertCommand).
+pub trait GpuInsertExclusive {
+    /// Data required to complete the `insert`.
+    /// It will be passed forth from the [`GpuInsertCommand`] issuing this `insert` to [`GpuInsertExclusive::insert`].
+    type Info: Clone + Send + Sync + 'static;
+
+    /// Insert data into the `MainWorld`.
+    fn insert(
+        data: &GpuInsertData,
+        info: Self::Info,
+        world: &mut World,
+    ) -> Result<(), GpuInsertError>;
+}
+
+/// Issues `inserts` for `T` implementing [`GpuInsertExclusive`], e.g. with [`GpuInsertCommand<ExclusiveGpuInsert<T>>`](GpuInsertCommand) and [`StagingNode<ExclusiveGpuInsert<T>>`](crate::StagingNode).
+pub struct ExclusiveGpuInsert<T>(PhantomData<fn() -> T>);
+
+impl<T> GpuInsertInfo for ExclusiveGpuInsert<T>
+where
+    T: GpuInsertExclusive,
+{
+    type Info = T::Info;
+}
+
+/// Clear completed [`GpuInsertCommands`](GpuInsertCommand) and [`GpuTextureInsertCommands`](GpuTextureInsertCommand).
 pub(crate) fn clear_gpu_insert_commands<T>(mut commands: Commands)
 where
-    T: GpuInsert,
+    T: GpuInsertInfo,
     T: 'static,
 {
     commands.insert_resource(Vec::<GpuInsertCommand<T>>::new());
+    commands.insert_resource(Vec::<GpuTextureInsertCommand<T>>::new());
 }
 
 /// Tries to conclude [`GpuInsertCommands`](GpuInsertCommand) for `T` by [`inserting`](GpuInsert::insert) data from staged (readable) buffers to the `MainWorld`.
-///
-/// Failed `inserts` will be scheduled for the next frame.
 pub(crate) fn insert<T>(
     transfer_receiver: Res<GpuInsertReceiver<T>>,
     mut insert_next_frame: ResMut<InsertNextFrame<T>>,
+    mut dead_letters: ResMut<GpuInsertDeadLetters<T>>,
+    mut outcomes: EventWriter<GpuInsertOutcome<T>>,
+    mut diagnostics: GpuInsertDiagnosticsParam<T>,
     param: StaticSystemParam<T::Param>,
 ) where
     T: GpuInsert,
     T: 'static,
 {
     let mut param = param.into_inner();
-    let mut queued_transfers = std::mem::take(&mut insert_next_frame.commands);
+    let mut measurements = GpuInsertMeasurements::default();
 
-    for command in queued_transfers
-        .drain(..)
-        .chain(transfer_receiver.try_iter())
-    {
-        let buffer_slice = command.staging_buffer.slice(
-            command.staging_buffer_offset
-                ..command.staging_buffer_offset + (command.bounds.end - command.bounds.start),
-        );
+    for outcome in conclude_inserts(
+        &transfer_receiver,
+        &mut insert_next_frame,
+        &mut dead_letters,
+        diagnostics.frame(),
+        &mut measurements,
+        |items| T::insert_batch(items.into_iter(), &mut param),
+    ) {
+        outcomes.send(outcome);
+    }
+
+    diagnostics.record(measurements, insert_next_frame.commands.len());
+}
+
+/// Tries to conclude [`GpuInsertCommands`](GpuInsertCommand) for [`ExclusiveGpuInsert<T>`] by [`inserting`](GpuInsertExclusive::insert) data from staged (readable) buffers to the `MainWorld`.
+pub(crate) fn insert_exclusive<T>(world: &mut World)
+where
+    T: GpuInsertExclusive,
+    T: 'static,
+{
+    let frame = world.resource::<GpuInsertFrame>().0;
+    let mut measurements = GpuInsertMeasurements::default();
 
-        let result = {
-            T::insert(
-                &buffer_slice.get_mapped_range(),
-                command.info.clone(),
-                &mut param,
+    let (outcomes, backlog) = world.resource_scope(
+        |world, transfer_receiver: Mut<GpuInsertReceiver<ExclusiveGpuInsert<T>>>| {
+            world.resource_scope(
+                |world, mut insert_next_frame: Mut<InsertNextFrame<ExclusiveGpuInsert<T>>>| {
+                    let outcomes =
+                            world.resource_scope(
+                                |world,
+                                 mut dead_letters: Mut<
+                                    GpuInsertDeadLetters<ExclusiveGpuInsert<T>>,
+                                >| {
+                                    conclude_inserts(
+                                        &transfer_receiver,
+                                        &mut insert_next_frame,
+                                        &mut dead_letters,
+                                        frame,
+                                        &mut measurements,
+                                        |items| {
+                                            items
+                                                .into_iter()
+                                                .map(|(data, info)| T::insert(&data, info, world))
+                                                .collect()
+                                        },
+                                    )
+                                },
+                            );
+
+                    (outcomes, insert_next_frame.commands.len())
+                },
             )
-        };
+        },
+    );
+
+    world.resource_scope(|world, mut diagnostics: Mut<Diagnostics>| {
+        let pending = world.resource::<GpuInsertPending<ExclusiveGpuInsert<T>>>();
+        measurements.record(&mut diagnostics, pending, backlog);
+    });
+
+    let mut events = world.resource_mut::<Events<GpuInsertOutcome<ExclusiveGpuInsert<T>>>>();
+    for outcome in outcomes {
+        events.send(outcome);
+    }
+}
+
+/// Tries to conclude [`GpuInsertCommands`](GpuInsertCommand) for `T` by calling `insert` once with data from every staged (readable) buffer.
+///
+/// Failed `inserts` will be scheduled for a later frame if they ask to be retried, otherwise they are discarded.
+/// `Inserts` exceeding the [`GpuInsertRetryPolicy`] or whose staging buffer could not be mapped are moved to the [`GpuInsertDeadLetters<T>`].
+/// Returns the outcome of every attempt, the latency and throughput of the `frame` are added to the `measurements`.
+fn conclude_inserts<T>(
+    transfer_receiver: &GpuInsertReceiver<T>,
+    insert_next_frame: &mut InsertNextFrame<T>,
+    dead_letters: &mut GpuInsertDeadLetters<T>,
+    frame: u64,
+    measurements: &mut GpuInsertMeasurements,
+    insert: impl FnOnce(Vec<(GpuInsertData, T::Info)>) -> Vec<Result<(), GpuInsertError>>,
+) -> Vec<GpuInsertOutcome<T>>
+where
+    T: GpuInsertInfo,
+{
+    let mut outcomes = Vec::new();
+    let mut queued_transfers = Vec::new();
+
+    for mut retry in std::mem::take(&mut insert_next_frame.commands) {
+        if retry.delay == 0 {
+            queued_transfers.push((retry.command, retry.staging, retry.meta));
+        } else {
+            retry.delay -= 1;
+            insert_next_frame.commands.push(retry);
+        }
+    }
+
+    for staged in transfer_receiver.try_iter() {
+        let frames = frame.wrapping_sub(staged.issued_frame);
+
+        match staged.result {
+            Ok(staging) => {
+                measurements.received += 1;
+                measurements.map_frames.push(frames);
+                measurements.staged_bytes += staging.range.end - staging.range.start;
 
-        match result {
+                let meta = GpuInsertMeta {
+                    issued_frame: staged.issued_frame,
+                    completed_frame: frame,
+                    byte_length: staging.range.end - staging.range.start,
+                    attempts: 0,
+                };
+                queued_transfers.push((staged.command, staging, meta));
+            }
+            // Invalid commands would fail again if re-dispatched, unlike commands held back by the `StagingNode`.
+            Err(err @ GpuInsertError::Invalid(_)) => {
+                error!("gpu insert failed: {}", err);
+                outcomes.push(GpuInsertOutcome::Failed(staged.command.info().clone(), err));
+            }
+            Err(err) => {
+                measurements.received += 1;
+                error!("gpu insert failed: {}", err);
+                outcomes.push(GpuInsertOutcome::Failed(staged.command.info().clone(), err));
+                dead_letters.commands.push(staged.command);
+            }
+        }
+    }
+
+    let results = {
+        let mapped_ranges: Vec<_> = queued_transfers
+            .iter()
+            .map(|(_, staging, _)| {
+                staging
+                    .buffer
+                    .slice(staging.range.clone())
+                    .get_mapped_range()
+            })
+            .collect();
+
+        let packed: Vec<_> = mapped_ranges
+            .iter()
+            .zip(queued_transfers.iter())
+            .map(|(data, (command, _, _))| command.pack(data))
+            .collect();
+
+        insert(
+            packed
+                .iter()
+                .zip(queued_transfers.iter())
+                .map(|(data, (command, _, meta))| {
+                    (command.data(data, *meta), command.info().clone())
+                })
+                .collect(),
+        )
+    };
+
+    let mut results = results.into_iter();
+
+    for (command, staging, mut meta) in queued_transfers.drain(..) {
+        let result = results.next().unwrap_or_else(|| {
+            Err(GpuInsertError::Failed(
+                "`insert_batch` returned too few results".into(),
+            ))
+        });
+
+        let delay = match result {
             Ok(_) => {
-                command.staging_buffer.unmap();
+                staging.release();
+                measurements
+                    .insert_frames
+                    .push(frame.wrapping_sub(meta.issued_frame));
+                outcomes.push(GpuInsertOutcome::Inserted(command.info().clone()));
+                continue;
+            }
+            Err(GpuInsertError::RetryNextUpdate) => 0,
+            Err(GpuInsertError::RetryAfter(frames)) => frames.saturating_sub(1),
+            Err(GpuInsertError::Discard | GpuInsertError::TargetRemoved) => {
+                staging.release();
+                outcomes.push(GpuInsertOutcome::Discarded(command.info().clone()));
+                continue;
             }
-            Err(GpuInsertError::RetryNextUpdate) => {
-                insert_next_frame.commands.push(command);
+            Err(err) => {
+                error!("gpu insert failed: {}", err);
+                staging.release();
+                outcomes.push(GpuInsertOutcome::Failed(command.info().clone(), err));
+                continue;
             }
+        };
+
+        meta.attempts += 1;
+        let attempts = meta.attempts;
+        let retry_policy = insert_next_frame.retry_policy;
+
+        if retry_policy.exceeded(attempts) {
+            staging.release();
+            outcomes.push(GpuInsertOutcome::Failed(
+                command.info().clone(),
+                GpuInsertError::RetriesExceeded(attempts),
+            ));
+            dead_letters.commands.push(command);
+            continue;
         }
+
+        outcomes.push(GpuInsertOutcome::Retried(command.info().clone()));
+        insert_next_frame.commands.push(RetryGpuInsert {
+            command,
+            staging,
+            delay: delay.max(retry_policy.backoff(attempts)),
+            meta,
+        });
+    }
+
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GpuInsertRetryPolicy;
+
+    #[test]
+    fn backoff_doubles_up_to_max_backoff() {
+        let policy = GpuInsertRetryPolicy {
+            max_attempts: None,
+            max_backoff: Some(5),
+        };
+
+        let backoff: Vec<_> = (1..=4).map(|attempts| policy.backoff(attempts)).collect();
+        assert_eq!(backoff, [0, 1, 3, 5]);
+        assert_eq!(policy.backoff(u32::MAX), 5);
+    }
+
+    #[test]
+    fn backoff_without_max_backoff_retries_as_requested() {
+        let policy = GpuInsertRetryPolicy::default();
+
+        assert!((1..=4).all(|attempts| policy.backoff(attempts) == 0));
+    }
+
+    #[test]
+    fn exceeded_after_max_attempts() {
+        let policy = GpuInsertRetryPolicy {
+            max_attempts: Some(3),
+            max_backoff: None,
+        };
+
+        let exceeded: Vec<_> = (1..=4).map(|attempts| policy.exceeded(attempts)).collect();
+        assert_eq!(exceeded, [false, false, true, true]);
+        assert!(!GpuInsertRetryPolicy::default().exceeded(u32::MAX));
     }
 }
@@ -0,0 +1,39 @@
+use bevy::{
+    ecs::system::{lifetimeless::SResMut, SystemParamItem},
+    prelude::{Assets, Handle, Image},
+};
+
+use crate::{GpuInsert, GpuInsertData, GpuInsertError};
+
+/// `Insert` the texels of a texture staged by a [`GpuTextureInsertCommand<GpuImageInsert>`](crate::GpuTextureInsertCommand) to the [`Handle<Image>`] passed as `Info`.
+///
+/// The data, size and format of the image are replaced with the tightly packed rows and their [`GpuTextureLayout`](crate::GpuTextureLayout), which sends an [`AssetEvent`](bevy::asset::AssetEvent) once the `insert` completed.
+/// The image has to exist, add a placeholder with the intended [`TextureDimension`](bevy::render::render_resource::TextureDimension) and sampler before issuing the `insert`.
+/// If the image was removed before the `insert`, the data is discarded and reported as [`GpuInsertOutcome::Discarded`](crate::GpuInsertOutcome::Discarded).
+pub struct GpuImageInsert;
+
+impl GpuInsert for GpuImageInsert {
+    type Info = Handle<Image>;
+    type Param = SResMut<Assets<Image>>;
+
+    fn insert(
+        data: &GpuInsertData,
+        handle: Self::Info,
+        images: &mut SystemParamItem<Self::Param>,
+    ) -> Result<(), GpuInsertError> {
+        let layout = data.texture().ok_or_else(|| {
+            GpuInsertError::Failed("`GpuImageInsert` requires a `GpuTextureInsertCommand`".into())
+        })?;
+
+        let image = images
+            .get_mut(&handle)
+            .ok_or(GpuInsertError::TargetRemoved)?;
+
+        image.texture_descriptor.size = layout.size;
+        image.texture_descriptor.format = layout.format;
+        image.texture_descriptor.mip_level_count = 1;
+        image.data = data.to_vec();
+
+        Ok(())
+    }
+}
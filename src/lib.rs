@@ -1,14 +1,75 @@
+pub use asset::GpuAssetInsert;
 use bevy::{
-    prelude::{App, CoreStage, Plugin},
+    prelude::{App, CoreStage, IntoExclusiveSystem, Plugin},
     render::{RenderApp, RenderStage},
 };
+#[cfg(feature = "derive")]
+pub use bevy_gpu_insert_derive::GpuInsert;
+pub use buffer::{ReadbackSource, StagingBuffer};
+pub use component::GpuComponentInsert;
 pub use compute::graph::StagingNode;
-use gpu_insert::{clear_gpu_insert_commands, insert};
-pub use gpu_insert::{GpuInsert, GpuInsertCommand, GpuInsertError, InsertNextFrame};
+pub use diagnostic::GpuInsertDiagnostics;
+use diagnostic::{register_gpu_insert_diagnostics, GpuInsertPending};
+pub use event::GpuDataReady;
+pub use frame::GpuInsertFrame;
+use frame::{count_frames, extract_frame};
+pub use from_gpu_bytes::FromGpuBytes;
+use gpu_insert::{clear_gpu_insert_commands, insert, insert_exclusive};
+pub use gpu_insert::{
+    AnyGpuInsertCommand, ExclusiveGpuInsert, GpuInsert, GpuInsertCommand, GpuInsertData,
+    GpuInsertDeadLetters, GpuInsertError, GpuInsertExclusive, GpuInsertInfo, GpuInsertMeta,
+    GpuInsertOutcome, GpuInsertRegion, GpuInsertRetryPolicy, GpuInsertSource, InsertNextFrame,
+    RetryGpuInsert, StagedGpuInsert,
+};
+pub use image::GpuImageInsert;
+use memory::HeldBackGpuInsertCommands;
+pub use memory::{GpuInsertStagingMemory, StagingMemory};
+#[cfg(feature = "bytemuck")]
+pub use pod::GpuInsertPod;
+use readback::resolve_failed_readbacks;
+pub use readback::{
+    GpuReadback, GpuReadbackCommand, GpuReadbackInsert, GpuReadbackSender, ReadbackError,
+};
+pub use registry::{GpuBufferId, GpuBufferRegistry};
+use request::{clear_gpu_insert_requests, extract_gpu_insert_requests};
+pub use request::{GpuInsertRequest, GpuInsertRequests};
+pub use shader_type::{GpuInsertShaderType, ShaderLayout, ShaderTypeGpuInsert};
+pub use staging::{StagingBufferPool, StagingRange};
 use std::marker::PhantomData;
+pub use subscription::{GpuReadbackSubscription, GpuReadbackSubscriptions, ReadbackCadence};
+pub use texture::{GpuTextureInsertCommand, GpuTextureLayout};
+pub use validation::GpuInsertValidationError;
 
+pub mod asset;
+pub mod buffer;
+pub mod component;
 pub mod compute;
+pub mod diagnostic;
+pub mod event;
+pub mod frame;
+pub mod from_gpu_bytes;
 pub mod gpu_insert;
+pub mod image;
+pub mod memory;
+#[cfg(feature = "bytemuck")]
+pub mod pod;
+pub mod readback;
+pub mod registry;
+pub mod request;
+pub mod shader_type;
+pub mod staging;
+pub mod subscription;
+pub mod texture;
+pub mod validation;
+
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+#[path = "private.rs"]
+pub mod __private;
+
+// Lets the derive refer to `::bevy_gpu_insert` within the tests of the crate itself.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as bevy_gpu_insert;
 
 /// [`Insert`](GpuInsert::insert) data to the `MainWorld` from buffers on the Gpu by issuing [`GpuInsertCommands<T>`](GpuInsertCommand) where `T` implements [`GpuInsert`].
 /// Data to be read will be copied to `staging_buffers` to be staged - making them readable by the Cpu.
@@ -16,15 +77,28 @@ pub struct GpuInsertPlugin<T>
 where
     T: GpuInsert,
 {
+    retry_policy: GpuInsertRetryPolicy,
     marker: PhantomData<fn() -> T>,
 }
 
+impl<T> GpuInsertPlugin<T>
+where
+    T: GpuInsert,
+{
+    /// Retry failed `inserts` following the `retry_policy`. Retries indefinitely by default.
+    pub fn with_retry_policy(mut self, retry_policy: GpuInsertRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+}
+
 impl<T> Default for GpuInsertPlugin<T>
 where
     T: GpuInsert,
 {
     fn default() -> Self {
         Self {
+            retry_policy: Default::default(),
             marker: PhantomData,
         }
     }
@@ -36,17 +110,148 @@ where
     T: 'static,
 {
     fn build(&self, app: &mut App) {
-        app.init_resource::<InsertNextFrame<T>>()
-            .add_system_to_stage(CoreStage::First, insert::<T>);
+        build_gpu_insert::<T>(app, self.retry_policy);
 
-        let (sender, receiver) = gpu_insert::create_transfer_channels::<T>();
-        app.insert_resource(receiver);
+        app.add_system_to_stage(CoreStage::First, insert::<T>);
+    }
+}
 
-        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+/// [`Insert`](GpuInsertExclusive::insert) data to the `MainWorld` with exclusive access to the `World` from buffers on the Gpu by issuing [`GpuInsertCommands<ExclusiveGpuInsert<T>>`](GpuInsertCommand) where `T` implements [`GpuInsertExclusive`].
+/// Data to be read will be copied to `staging_buffers` to be staged by the [`StagingNode<ExclusiveGpuInsert<T>>`](StagingNode) - making them readable by the Cpu.
+pub struct GpuInsertExclusivePlugin<T>
+where
+    T: GpuInsertExclusive,
+{
+    retry_policy: GpuInsertRetryPolicy,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> GpuInsertExclusivePlugin<T>
+where
+    T: GpuInsertExclusive,
+{
+    /// Retry failed `inserts` following the `retry_policy`. Retries indefinitely by default.
+    pub fn with_retry_policy(mut self, retry_policy: GpuInsertRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+}
+
+impl<T> Default for GpuInsertExclusivePlugin<T>
+where
+    T: GpuInsertExclusive,
+{
+    fn default() -> Self {
+        Self {
+            retry_policy: Default::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Plugin for GpuInsertExclusivePlugin<T>
+where
+    T: GpuInsertExclusive,
+    T: 'static,
+{
+    fn build(&self, app: &mut App) {
+        build_gpu_insert::<ExclusiveGpuInsert<T>>(app, self.retry_policy);
+
+        app.add_system_to_stage(CoreStage::First, insert_exclusive::<T>.exclusive_system());
+    }
+}
+
+/// Add the resources and systems shared by every kind of `insert`.
+fn build_gpu_insert<T>(app: &mut App, retry_policy: GpuInsertRetryPolicy)
+where
+    T: GpuInsertInfo,
+    T: 'static,
+{
+    app.insert_resource(InsertNextFrame::<T>::new(retry_policy))
+        .init_resource::<GpuInsertDeadLetters<T>>()
+        .init_resource::<GpuInsertRequests<T>>()
+        .add_event::<GpuInsertOutcome<T>>()
+        .add_system_to_stage(CoreStage::First, clear_gpu_insert_requests::<T>);
+
+    // Frames are counted once for every kind of `insert`.
+    let frames_counted = app.world.contains_resource::<GpuInsertFrame>();
+    if !frames_counted {
+        app.init_resource::<GpuInsertFrame>()
+            .add_system_to_stage(CoreStage::First, count_frames.exclusive_system().at_start());
+    }
+
+    register_gpu_insert_diagnostics::<T>(app);
+
+    let pending = GpuInsertPending::<T>::default();
+    let staging_memory = app
+        .world
+        .get_resource_or_insert_with(StagingMemory::default)
+        .clone();
+    let insert_staging_memory = GpuInsertStagingMemory::<T>::default();
+    let subscriptions = GpuReadbackSubscriptions::<T>::default();
+    app.insert_resource(insert_staging_memory.clone())
+        .insert_resource(subscriptions.clone());
+
+    let (sender, receiver) = gpu_insert::create_transfer_channels::<T>();
+    app.insert_resource(receiver)
+        .insert_resource(pending.clone());
+
+    if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+        if !frames_counted {
             render_app
-                .insert_resource(sender)
-                .init_resource::<Vec<GpuInsertCommand<T>>>()
-                .add_system_to_stage(RenderStage::Cleanup, clear_gpu_insert_commands::<T>);
+                .init_resource::<GpuInsertFrame>()
+                .add_system_to_stage(RenderStage::Extract, extract_frame);
+        }
+
+        render_app
+            .insert_resource(sender)
+            .insert_resource(pending)
+            .insert_resource(staging_memory)
+            .insert_resource(insert_staging_memory)
+            .insert_resource(subscriptions)
+            .init_resource::<HeldBackGpuInsertCommands<T>>()
+            .init_resource::<Vec<GpuInsertCommand<T>>>()
+            .init_resource::<Vec<GpuTextureInsertCommand<T>>>()
+            .init_resource::<Vec<GpuInsertRequest<T>>>()
+            .init_resource::<StagingBufferPool>()
+            .init_resource::<GpuBufferRegistry>()
+            .add_system_to_stage(RenderStage::Extract, extract_gpu_insert_requests::<T>)
+            .add_system_to_stage(RenderStage::Cleanup, clear_gpu_insert_commands::<T>);
+    }
+}
+
+/// Send data from buffers on the Gpu as [`GpuDataReady<K>`] events by issuing [`GpuInsertCommands<GpuDataReady<K>>`](GpuInsertCommand) whose `info` is the `key` of the event.
+/// Data to be read will be copied to `staging_buffers` to be staged by the [`StagingNode<GpuDataReady<K>>`](StagingNode) - making them readable by the Cpu.
+pub struct GpuDataReadyPlugin<K> {
+    marker: PhantomData<fn() -> K>,
+}
+
+impl<K> Default for GpuDataReadyPlugin<K> {
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
         }
     }
 }
+
+impl<K> Plugin for GpuDataReadyPlugin<K>
+where
+    K: Clone + Send + Sync + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.add_event::<GpuDataReady<K>>()
+            .add_plugin(GpuInsertPlugin::<GpuDataReady<K>>::default());
+    }
+}
+
+/// Read data from buffers on the Gpu by issuing [`GpuReadbackCommands`](GpuReadbackCommand) and awaiting their [`GpuReadbacks`](GpuReadback).
+/// Data to be read will be copied to `staging_buffers` to be staged by the [`StagingNode<GpuReadbackInsert>`](StagingNode) - making them readable by the Cpu.
+#[derive(Default)]
+pub struct GpuReadbackPlugin;
+
+impl Plugin for GpuReadbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(GpuInsertPlugin::<GpuReadbackInsert>::default())
+            .add_system_to_stage(CoreStage::PreUpdate, resolve_failed_readbacks);
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    collections::VecDeque,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use bevy::render::render_resource::BufferAddress;
+
+use crate::{subscription::SubscriptionReadback, AnyGpuInsertCommand, GpuInsertInfo};
+
+/// Bytes staged by every [`StagingNode`](crate::StagingNode) whose staging buffers are in flight or mapped, shared by the `MainWorld` and the `RenderWorld`.
+///
+/// Commands exceeding the `cap` are held back by the [`StagingNode`](crate::StagingNode) until enough staging memory was released.
+/// A command is staged regardless of the `cap` if nothing else is staged, so commands larger than the `cap` are not held back forever.
+/// Commands staged in a pooled staging buffer account for the whole buffer, and free pooled buffers are dropped while the [`StagingBufferPool`](crate::StagingBufferPool) holds more than the `cap`.
+/// Insert a [`StagingMemory::with_cap`] before adding the plugins, or change the cap at any time with [`StagingMemory::set_cap`].
+#[derive(Clone)]
+pub struct StagingMemory(Arc<StagingMemoryInner>);
+
+struct StagingMemoryInner {
+    bytes: AtomicU64,
+    /// `BufferAddress::MAX` if there is no cap.
+    cap: AtomicU64,
+}
+
+impl StagingMemory {
+    /// Hold back commands once `cap` bytes are staged.
+    pub fn with_cap(cap: BufferAddress) -> Self {
+        let memory = Self::default();
+        memory.set_cap(Some(cap));
+        memory
+    }
+
+    /// Bytes staged for every kind of `insert`.
+    pub fn bytes(&self) -> BufferAddress {
+        self.0.bytes.load(Ordering::Acquire)
+    }
+
+    pub fn cap(&self) -> Option<BufferAddress> {
+        match self.0.cap.load(Ordering::Relaxed) {
+            BufferAddress::MAX => None,
+            cap => Some(cap),
+        }
+    }
+
+    pub fn set_cap(&self, cap: Option<BufferAddress>) {
+        self.0
+            .cap
+            .store(cap.unwrap_or(BufferAddress::MAX), Ordering::Relaxed);
+    }
+
+    /// Whether `size` more bytes may be staged.
+    pub(crate) fn fits(&self, size: BufferAddress) -> bool {
+        let bytes = self.bytes();
+        bytes == 0 || bytes.saturating_add(size) <= self.0.cap.load(Ordering::Relaxed)
+    }
+
+    /// Account for `size` bytes staged for `inserts` of `T` until the returned reservation is dropped.
+    pub(crate) fn reserve<T>(
+        &self,
+        memory: &GpuInsertStagingMemory<T>,
+        size: BufferAddress,
+    ) -> StagingReservation {
+        self.0.bytes.fetch_add(size, Ordering::AcqRel);
+        memory.bytes.fetch_add(size, Ordering::AcqRel);
+
+        StagingReservation {
+            size,
+            global: self.clone(),
+            bytes: memory.bytes.clone(),
+        }
+    }
+}
+
+impl Default for StagingMemory {
+    fn default() -> Self {
+        Self(Arc::new(StagingMemoryInner {
+            bytes: AtomicU64::new(0),
+            cap: AtomicU64::new(BufferAddress::MAX),
+        }))
+    }
+}
+
+/// Bytes staged for `inserts` of `T` whose staging buffers are in flight or mapped, shared by the `MainWorld` and the `RenderWorld`.
+pub struct GpuInsertStagingMemory<T> {
+    bytes: Arc<AtomicU64>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> GpuInsertStagingMemory<T> {
+    pub fn bytes(&self) -> BufferAddress {
+        self.bytes.load(Ordering::Acquire)
+    }
+}
+
+impl<T> Default for GpuInsertStagingMemory<T> {
+    fn default() -> Self {
+        Self {
+            bytes: Default::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for GpuInsertStagingMemory<T> {
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Bytes accounted for in the [`StagingMemory`] and the [`GpuInsertStagingMemory<T>`] until dropped.
+pub(crate) struct StagingReservation {
+    size: BufferAddress,
+    global: StagingMemory,
+    bytes: Arc<AtomicU64>,
+}
+
+impl Drop for StagingReservation {
+    fn drop(&mut self) {
+        self.global.0.bytes.fetch_sub(self.size, Ordering::AcqRel);
+        self.bytes.fetch_sub(self.size, Ordering::AcqRel);
+    }
+}
+
+/// Commands held back by the [`StagingNode<T>`](crate::StagingNode) while the [`StagingMemory`] is exceeding its cap, staged in order once memory was released.
+///
+/// Commands whose `staging_buffer` is still mapped for earlier commands are held back as well, until the buffer was unmapped.
+pub(crate) struct HeldBackGpuInsertCommands<T>
+where
+    T: GpuInsertInfo,
+{
+    /// Commands together with the readback of their subscription, if any.
+    pub(crate) commands: Mutex<VecDeque<(AnyGpuInsertCommand<T>, Option<SubscriptionReadback>)>>,
+}
+
+impl<T> Default for HeldBackGpuInsertCommands<T>
+where
+    T: GpuInsertInfo,
+{
+    fn default() -> Self {
+        Self {
+            commands: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GpuInsertStagingMemory, StagingMemory};
+
+    struct Meshes;
+    struct Counters;
+
+    #[test]
+    fn fits_within_cap() {
+        let memory = StagingMemory::with_cap(100);
+        let meshes = GpuInsertStagingMemory::<Meshes>::default();
+
+        let _reservation = memory.reserve(&meshes, 60);
+
+        assert!(memory.fits(40), "exactly at the cap");
+        assert!(!memory.fits(41), "over the cap");
+    }
+
+    #[test]
+    fn fits_oversized_if_nothing_is_staged() {
+        let memory = StagingMemory::with_cap(100);
+        let meshes = GpuInsertStagingMemory::<Meshes>::default();
+
+        assert!(memory.fits(1000));
+
+        let _reservation = memory.reserve(&meshes, 1000);
+        assert!(!memory.fits(1));
+    }
+
+    #[test]
+    fn fits_anything_without_cap() {
+        let memory = StagingMemory::default();
+        let meshes = GpuInsertStagingMemory::<Meshes>::default();
+
+        let _reservation = memory.reserve(&meshes, 1000);
+        assert_eq!(memory.cap(), None);
+        assert!(memory.fits(u64::MAX));
+    }
+
+    #[test]
+    fn dropped_reservations_are_released() {
+        let memory = StagingMemory::with_cap(100);
+        let meshes = GpuInsertStagingMemory::<Meshes>::default();
+        let counters = GpuInsertStagingMemory::<Counters>::default();
+
+        let mesh_reservation = memory.reserve(&meshes, 60);
+        let counter_reservation = memory.reserve(&counters, 40);
+
+        assert_eq!(memory.bytes(), 100);
+        assert_eq!(meshes.bytes(), 60);
+        assert_eq!(counters.bytes(), 40);
+        assert!(!memory.fits(1));
+
+        drop(mesh_reservation);
+
+        assert_eq!(memory.bytes(), 40);
+        assert_eq!(meshes.bytes(), 0);
+        assert_eq!(counters.bytes(), 40);
+        assert!(memory.fits(60));
+
+        drop(counter_reservation);
+
+        assert_eq!(memory.bytes(), 0);
+        assert_eq!(counters.bytes(), 0);
+    }
+}
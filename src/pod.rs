@@ -0,0 +1,56 @@
+use bevy::ecs::system::{SystemParam, SystemParamItem};
+use bytemuck::{Pod, PodCastError};
+
+use crate::{GpuInsert, GpuInsertData, GpuInsertError};
+
+/// `Insert` typed data to the `MainWorld` from staged (readable) buffers on the Gpu.
+///
+/// Staged data is cast to a slice of [`Element`](GpuInsertPod::Element) before it is passed to [`GpuInsertPod::insert`].
+/// Every type implementing [`GpuInsertPod`] implements [`GpuInsert`] and is driven by the same [`GpuInsertPlugin`](crate::GpuInsertPlugin).
+pub trait GpuInsertPod {
+    /// Type the staged data is read as.
+    type Element: Pod;
+    /// Data required to complete the `insert`.
+    /// It will be passed forth from the [`GpuInsertCommand`](crate::GpuInsertCommand) issuing this `insert` to [`GpuInsertPod::insert`].
+    type Info: Clone + Send + Sync + 'static;
+    /// Access ECS data required to complete the `insert` within [`GpuInsertPod::insert`].
+    /// Use [`lifetimeless`](bevy::ecs::system::lifetimeless) [`SystemParam`] for convenience.
+    type Param: SystemParam;
+
+    /// Insert typed data into the `MainWorld`.
+    fn insert(
+        data: &[Self::Element],
+        info: Self::Info,
+        param: &mut SystemParamItem<Self::Param>,
+    ) -> Result<(), GpuInsertError>;
+}
+
+impl<T> GpuInsert for T
+where
+    T: GpuInsertPod,
+{
+    type Info = <T as GpuInsertPod>::Info;
+    type Param = <T as GpuInsertPod>::Param;
+
+    fn insert(
+        data: &GpuInsertData,
+        info: Self::Info,
+        param: &mut SystemParamItem<Self::Param>,
+    ) -> Result<(), GpuInsertError> {
+        let data = bytemuck::try_cast_slice(data.bytes()).map_err(GpuInsertError::from)?;
+
+        <T as GpuInsertPod>::insert(data, info, param)
+    }
+}
+
+impl From<PodCastError> for GpuInsertError {
+    fn from(err: PodCastError) -> Self {
+        match err {
+            PodCastError::TargetAlignmentGreaterAndInputNotAligned
+            | PodCastError::AlignmentMismatch => GpuInsertError::Misaligned,
+            PodCastError::OutputSliceWouldHaveSlop | PodCastError::SizeMismatch => {
+                GpuInsertError::InvalidLength
+            }
+        }
+    }
+}
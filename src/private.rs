@@ -0,0 +1,89 @@
+pub use crate::{asset::insert_asset, component::insert_component};
+pub use bevy;
+
+use bytemuck::Pod;
+use std::mem::{align_of, size_of};
+
+/// Read a field of type `F` placed after the previous field ending at `end` following the `#[repr(C)]` layout.
+pub fn read_field<F>(data: &[u8], end: &mut usize) -> F
+where
+    F: Pod,
+{
+    let offset = end.next_multiple_of(align_of::<F>());
+    *end = offset + size_of::<F>();
+
+    bytemuck::pod_read_unaligned(&data[offset..*end])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::{offset_of, size_of};
+
+    use super::read_field;
+
+    #[repr(C)]
+    struct Padded {
+        a: u8,
+        b: f32,
+    }
+
+    #[test]
+    fn read_field_skips_padding() {
+        let mut data = [0xff; size_of::<Padded>()];
+        data[offset_of!(Padded, a)] = 7;
+        data[offset_of!(Padded, b)..][..4].copy_from_slice(&1.5f32.to_ne_bytes());
+
+        let mut end = 0;
+        assert_eq!(read_field::<u8>(&data, &mut end), 7);
+        assert_eq!(read_field::<f32>(&data, &mut end), 1.5);
+        assert_eq!(end, size_of::<Padded>());
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod derive_tests {
+    use std::mem::{offset_of, size_of};
+
+    use bevy::prelude::Component;
+
+    use crate::{FromGpuBytes, GpuInsert, GpuInsertError};
+
+    #[derive(GpuInsert, Component, Debug, PartialEq)]
+    #[gpu_insert(component)]
+    #[repr(C)]
+    struct Padded {
+        a: u8,
+        b: [f32; 3],
+    }
+
+    #[test]
+    fn from_gpu_bytes_follows_repr_c() {
+        let mut data = [0xff; size_of::<Padded>()];
+        data[offset_of!(Padded, a)] = 7;
+        for (index, value) in [1.0f32, 2.5, -4.0].iter().enumerate() {
+            data[offset_of!(Padded, b) + index * 4..][..4].copy_from_slice(&value.to_ne_bytes());
+        }
+
+        assert_eq!(
+            Padded::from_gpu_bytes(&data).unwrap(),
+            Padded {
+                a: 7,
+                b: [1.0, 2.5, -4.0],
+            }
+        );
+    }
+
+    #[test]
+    fn from_gpu_bytes_rejects_invalid_length() {
+        let data = [0; size_of::<Padded>() + 4];
+
+        assert!(matches!(
+            Padded::from_gpu_bytes(&data[..size_of::<Padded>() - 4]),
+            Err(GpuInsertError::InvalidLength)
+        ));
+        assert!(matches!(
+            Padded::from_gpu_bytes(&data),
+            Err(GpuInsertError::InvalidLength)
+        ));
+    }
+}
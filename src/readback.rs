@@ -0,0 +1,131 @@
+use std::{
+    fmt,
+    future::Future,
+    ops::Range,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use bevy::{
+    ecs::system::SystemParamItem,
+    prelude::{EventReader, ResMut},
+    render::render_resource::BufferAddress,
+};
+use futures_channel::oneshot;
+
+use crate::{
+    GpuInsert, GpuInsertCommand, GpuInsertData, GpuInsertDeadLetters, GpuInsertError,
+    GpuInsertOutcome, GpuInsertSource, GpuInsertValidationError,
+};
+
+/// Data of a [`GpuReadback`] or why it could not be read.
+pub type ReadbackResult = Result<Vec<u8>, ReadbackError>;
+
+#[derive(Debug, Clone)]
+pub enum ReadbackError {
+    /// Mapping the `staging_buffer` failed.
+    Map,
+    /// The [`GpuReadbackCommand`] was rejected by the [`StagingNode<GpuReadbackInsert>`](crate::StagingNode).
+    Invalid(GpuInsertValidationError),
+    /// The [`GpuReadbackCommand`] was dropped before its data was staged, e.g. because no [`StagingNode<GpuReadbackInsert>`](crate::StagingNode) is part of the `RenderGraph`.
+    Dropped,
+}
+
+impl fmt::Display for ReadbackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadbackError::Map => write!(f, "failed to map the staging buffer"),
+            ReadbackError::Invalid(err) => write!(f, "invalid command: {}", err),
+            ReadbackError::Dropped => write!(f, "the readback was dropped before it was staged"),
+        }
+    }
+}
+
+impl std::error::Error for ReadbackError {}
+
+/// Issues the `inserts` resolving [`GpuReadbacks`](GpuReadback), added by the [`GpuReadbackPlugin`](crate::GpuReadbackPlugin) and staged by the [`StagingNode<GpuReadbackInsert>`](crate::StagingNode).
+pub struct GpuReadbackInsert;
+
+impl GpuInsert for GpuReadbackInsert {
+    type Info = GpuReadbackSender;
+    type Param = ();
+
+    fn insert(
+        data: &GpuInsertData,
+        sender: Self::Info,
+        _param: &mut SystemParamItem<Self::Param>,
+    ) -> Result<(), GpuInsertError> {
+        sender.send(Ok(data.bytes().to_vec()));
+        Ok(())
+    }
+}
+
+/// Resolves the [`GpuReadback`] of a [`GpuReadbackCommand`] once.
+///
+/// Shared by the copies of the command, which is cloned whenever it is staged.
+#[derive(Clone)]
+pub struct GpuReadbackSender(Arc<Mutex<Option<oneshot::Sender<ReadbackResult>>>>);
+
+impl GpuReadbackSender {
+    fn send(&self, result: ReadbackResult) {
+        // The `GpuReadback` may have been dropped in the meantime.
+        if let Some(sender) = self.0.lock().unwrap().take() {
+            let _ = sender.send(result);
+        }
+    }
+}
+
+/// Command reading data back through a [`GpuReadback`] instead of implementing [`GpuInsert`].
+///
+/// Dispatched by pushing it to the resource [`Vec<GpuReadbackCommand>`] in the `RenderWorld`.
+pub type GpuReadbackCommand = GpuInsertCommand<GpuReadbackInsert>;
+
+impl GpuInsertCommand<GpuReadbackInsert> {
+    /// Create a [`GpuReadbackCommand`] copying data from `buffer` within the `bounds` and the [`GpuReadback`] resolving with its data.
+    ///
+    /// The data is staged in a pooled staging buffer, or in a given one with [`GpuInsertCommand::with_staging_buffer`].
+    pub fn readback(
+        buffer: impl Into<GpuInsertSource>,
+        bounds: Range<BufferAddress>,
+    ) -> (Self, GpuReadback) {
+        let (sender, receiver) = oneshot::channel();
+        let sender = GpuReadbackSender(Arc::new(Mutex::new(Some(sender))));
+
+        (Self::new(buffer, bounds, sender), GpuReadback(receiver))
+    }
+}
+
+/// Future resolving with the data of a [`GpuReadbackCommand`] as soon as its `staging_buffer` is staged (readable).
+///
+/// Can be awaited on any thread, e.g. by tasks spawned on Bevy's task pools.
+pub struct GpuReadback(oneshot::Receiver<ReadbackResult>);
+
+impl Future for GpuReadback {
+    type Output = ReadbackResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0)
+            .poll(cx)
+            .map(|result| result.unwrap_or(Err(ReadbackError::Dropped)))
+    }
+}
+
+/// Resolve the [`GpuReadbacks`](GpuReadback) of failed commands with their error.
+///
+/// Their dead letters are dropped, as a re-dispatched command could not resolve its [`GpuReadback`] again.
+pub(crate) fn resolve_failed_readbacks(
+    mut outcomes: EventReader<GpuInsertOutcome<GpuReadbackInsert>>,
+    mut dead_letters: ResMut<GpuInsertDeadLetters<GpuReadbackInsert>>,
+) {
+    for outcome in outcomes.iter() {
+        if let GpuInsertOutcome::Failed(sender, err) = outcome {
+            sender.send(Err(match err {
+                GpuInsertError::Invalid(err) => ReadbackError::Invalid(err.clone()),
+                _ => ReadbackError::Map,
+            }));
+        }
+    }
+
+    dead_letters.commands.clear();
+}
@@ -0,0 +1,62 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::ReadbackSource;
+
+/// Stable identifier of a buffer on the Gpu, which can be named in the `MainWorld` before the buffer exists.
+///
+/// Whoever owns the buffer registers it in the [`GpuBufferRegistry`] in the `RenderWorld`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GpuBufferId(GpuBufferIdRepr);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum GpuBufferIdRepr {
+    Unique(u64),
+    Label(&'static str),
+}
+
+impl GpuBufferId {
+    /// Create a new unique identifier.
+    pub fn new() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+
+        Self(GpuBufferIdRepr::Unique(
+            NEXT.fetch_add(1, Ordering::Relaxed),
+        ))
+    }
+
+    /// Identify a buffer by a well-known `label`, e.g. to be shared as a constant between plugins.
+    pub const fn from_label(label: &'static str) -> Self {
+        Self(GpuBufferIdRepr::Label(label))
+    }
+}
+
+impl Default for GpuBufferId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Live buffers in the `RenderWorld` by their [`GpuBufferId`], resolved by the [`StagingNode`](crate::StagingNode) when it runs.
+///
+/// Remove buffers once they are dropped, readbacks of removed buffers are rejected.
+#[derive(Default)]
+pub struct GpuBufferRegistry {
+    buffers: HashMap<GpuBufferId, ReadbackSource>,
+}
+
+impl GpuBufferRegistry {
+    pub fn insert(&mut self, id: GpuBufferId, buffer: ReadbackSource) -> Option<ReadbackSource> {
+        self.buffers.insert(id, buffer)
+    }
+
+    pub fn remove(&mut self, id: GpuBufferId) -> Option<ReadbackSource> {
+        self.buffers.remove(&id)
+    }
+
+    pub fn get(&self, id: GpuBufferId) -> Option<&ReadbackSource> {
+        self.buffers.get(&id)
+    }
+}
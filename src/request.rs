@@ -0,0 +1,86 @@
+use std::ops::Range;
+
+use bevy::{
+    prelude::{Commands, Res, ResMut},
+    render::{render_resource::BufferAddress, Extract},
+};
+
+use crate::{registry::GpuBufferId, GpuInsertInfo};
+
+/// Data to read back from the `MainWorld` for the [`GpuInsertPlugin<T>`](crate::GpuInsertPlugin), without writing systems for the `RenderWorld`.
+///
+/// Data from the buffer registered as `buffer` in the [`GpuBufferRegistry`](crate::GpuBufferRegistry) within the `bounds` is staged in a pooled staging buffer, then `inserted` with the `info`.
+/// Requests for buffers missing from the registry are rejected with [`GpuInsertValidationError::MissingBuffer`](crate::GpuInsertValidationError::MissingBuffer).
+pub struct GpuInsertRequest<T>
+where
+    T: GpuInsertInfo,
+{
+    pub buffer: GpuBufferId,
+    pub bounds: Range<BufferAddress>,
+    pub info: T::Info,
+}
+
+impl<T> Clone for GpuInsertRequest<T>
+where
+    T: GpuInsertInfo,
+{
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer,
+            bounds: self.bounds.clone(),
+            info: self.info.clone(),
+        }
+    }
+}
+
+/// [`GpuInsertRequests`](GpuInsertRequest) issued this frame in the `MainWorld`, extracted to the `RenderWorld` automatically.
+pub struct GpuInsertRequests<T>
+where
+    T: GpuInsertInfo,
+{
+    pub requests: Vec<GpuInsertRequest<T>>,
+}
+
+impl<T> GpuInsertRequests<T>
+where
+    T: GpuInsertInfo,
+{
+    pub fn push(&mut self, buffer: GpuBufferId, bounds: Range<BufferAddress>, info: T::Info) {
+        self.requests.push(GpuInsertRequest {
+            buffer,
+            bounds,
+            info,
+        });
+    }
+}
+
+impl<T> Default for GpuInsertRequests<T>
+where
+    T: GpuInsertInfo,
+{
+    fn default() -> Self {
+        Self {
+            requests: Default::default(),
+        }
+    }
+}
+
+/// Clear extracted [`GpuInsertRequests`](GpuInsertRequest) in the `MainWorld`.
+pub(crate) fn clear_gpu_insert_requests<T>(mut requests: ResMut<GpuInsertRequests<T>>)
+where
+    T: GpuInsertInfo,
+    T: 'static,
+{
+    requests.requests.clear();
+}
+
+/// Extract [`GpuInsertRequests`](GpuInsertRequest) to be resolved by the [`StagingNode<T>`](crate::StagingNode).
+pub(crate) fn extract_gpu_insert_requests<T>(
+    mut commands: Commands,
+    requests: Extract<Res<GpuInsertRequests<T>>>,
+) where
+    T: GpuInsertInfo,
+    T: 'static,
+{
+    commands.insert_resource(requests.requests.clone());
+}
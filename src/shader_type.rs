@@ -0,0 +1,226 @@
+use std::{marker::PhantomData, ops::Range};
+
+use bevy::{
+    ecs::system::{SystemParam, SystemParamItem},
+    render::render_resource::{
+        encase::{internal::CreateFrom, StorageBuffer, UniformBuffer},
+        BufferAddress, ShaderSize,
+    },
+};
+
+use crate::{GpuInsert, GpuInsertCommand, GpuInsertData, GpuInsertError, GpuInsertSource};
+
+/// Alignment of the values of a `DynamicUniformBuffer`, see `encase::DynamicUniformBuffer::new`.
+const DYNAMIC_UNIFORM_ALIGNMENT: BufferAddress = 256;
+
+/// Layout values were written to a buffer with, see [WGSL memory layout](https://gpuweb.github.io/gpuweb/wgsl/#memory-layouts).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShaderLayout {
+    /// Values in the storage address space (std430), stored one after another with the stride of an array.
+    Storage,
+    /// Values in the uniform address space (std140), stored at offsets aligned like a `DynamicUniformBuffer`.
+    Uniform,
+}
+
+impl ShaderLayout {
+    /// Bytes from the start of one value of type `V` to the start of the next.
+    pub fn stride<V>(self) -> BufferAddress
+    where
+        V: ShaderSize,
+    {
+        match self {
+            ShaderLayout::Storage => <[V; 1] as ShaderSize>::SHADER_SIZE.get(),
+            ShaderLayout::Uniform => V::SHADER_SIZE
+                .get()
+                .next_multiple_of(DYNAMIC_UNIFORM_ALIGNMENT),
+        }
+    }
+
+    /// Decode every value of type `V` within `data`, ignoring trailing padding.
+    fn decode<V>(self, data: &[u8]) -> Result<Vec<V>, GpuInsertError>
+    where
+        V: ShaderSize + CreateFrom,
+    {
+        let size = V::SHADER_SIZE.get() as usize;
+        let stride = self.stride::<V>() as usize;
+
+        if data.len() < size {
+            return Err(GpuInsertError::InvalidLength);
+        }
+
+        let count = (data.len() - size) / stride + 1;
+
+        (0..count)
+            .map(|index| {
+                let value = &data[index * stride..index * stride + size];
+
+                match self {
+                    ShaderLayout::Storage => StorageBuffer::new(value).create(),
+                    ShaderLayout::Uniform => UniformBuffer::new(value).create(),
+                }
+                .map_err(|_| GpuInsertError::InvalidLength)
+            })
+            .collect()
+    }
+}
+
+/// `Insert` values of a [`ShaderType`](bevy::render::render_resource::ShaderType) to the `MainWorld` from staged (readable) buffers on the Gpu.
+///
+/// Staged data is decoded with encase following the [`LAYOUT`](GpuInsertShaderType::LAYOUT), so the padding of std430 and std140 is skipped.
+/// Every region of the [`GpuInsertCommand`] is decoded into one value or several values one `stride` apart, passed to [`GpuInsertShaderType::insert`] in order.
+/// Issue these `inserts` with [`GpuInsertCommands<ShaderTypeGpuInsert<T>>`](GpuInsertCommand), e.g. created with [`GpuInsertCommand::value`] or [`GpuInsertCommand::elements`].
+pub trait GpuInsertShaderType {
+    /// Type the staged data is decoded as.
+    type Value: ShaderSize + CreateFrom;
+    /// Data required to complete the `insert`.
+    /// It will be passed forth from the [`GpuInsertCommand`] issuing this `insert` to [`GpuInsertShaderType::insert`].
+    type Info: Clone + Send + Sync + 'static;
+    /// Access ECS data required to complete the `insert` within [`GpuInsertShaderType::insert`].
+    /// Use [`lifetimeless`](bevy::ecs::system::lifetimeless) [`SystemParam`] for convenience.
+    type Param: SystemParam;
+
+    /// Layout the values were written with.
+    const LAYOUT: ShaderLayout = ShaderLayout::Storage;
+
+    /// Insert decoded values into the `MainWorld`.
+    fn insert(
+        values: Vec<Self::Value>,
+        info: Self::Info,
+        param: &mut SystemParamItem<Self::Param>,
+    ) -> Result<(), GpuInsertError>;
+}
+
+/// Issues `inserts` for `T` implementing [`GpuInsertShaderType`], e.g. with [`GpuInsertPlugin<ShaderTypeGpuInsert<T>>`](crate::GpuInsertPlugin) and [`StagingNode<ShaderTypeGpuInsert<T>>`](crate::StagingNode).
+pub struct ShaderTypeGpuInsert<T>(PhantomData<fn() -> T>);
+
+impl<T> GpuInsert for ShaderTypeGpuInsert<T>
+where
+    T: GpuInsertShaderType,
+{
+    type Info = T::Info;
+    type Param = T::Param;
+
+    fn insert(
+        data: &GpuInsertData,
+        info: Self::Info,
+        param: &mut SystemParamItem<Self::Param>,
+    ) -> Result<(), GpuInsertError> {
+        let mut values = Vec::new();
+        for region in data.regions() {
+            values.extend(T::LAYOUT.decode::<T::Value>(region)?);
+        }
+
+        T::insert(values, info, param)
+    }
+}
+
+impl<T> GpuInsertCommand<ShaderTypeGpuInsert<T>>
+where
+    T: GpuInsertShaderType,
+{
+    /// Command copying the value starting at `offset` in `buffer`, e.g. a [`ReadbackSource`](crate::ReadbackSource) written with encase's `StorageBuffer` or `UniformBuffer` at `offset` 0.
+    pub fn value(buffer: impl Into<GpuInsertSource>, offset: BufferAddress, info: T::Info) -> Self {
+        Self::new(buffer, offset..offset + T::Value::SHADER_SIZE.get(), info)
+    }
+
+    /// Command copying the values at the indices within `elements`, e.g. of a [`ReadbackSource`](crate::ReadbackSource) holding an array written with encase's `StorageBuffer` or values written with its `DynamicUniformBuffer`.
+    pub fn elements(
+        buffer: impl Into<GpuInsertSource>,
+        elements: Range<u64>,
+        info: T::Info,
+    ) -> Self {
+        let stride = T::LAYOUT.stride::<T::Value>();
+        let start = elements.start * stride;
+        // The last value is not followed by padding.
+        let end = if elements.is_empty() {
+            start
+        } else {
+            (elements.end - 1) * stride + T::Value::SHADER_SIZE.get()
+        };
+
+        Self::new(buffer, start..end, info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::SystemParamItem, math::Vec3, render::render_resource::ShaderSize};
+
+    use super::{GpuInsertShaderType, ShaderLayout, ShaderTypeGpuInsert};
+    use crate::{GpuBufferId, GpuInsertCommand, GpuInsertError};
+
+    struct Positions;
+
+    impl GpuInsertShaderType for Positions {
+        type Value = Vec3;
+        type Info = ();
+        type Param = ();
+
+        fn insert(
+            _values: Vec<Self::Value>,
+            _info: Self::Info,
+            _param: &mut SystemParamItem<Self::Param>,
+        ) -> Result<(), GpuInsertError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn stride() {
+        assert_eq!(ShaderLayout::Uniform.stride::<f32>(), 256);
+        assert_eq!(ShaderLayout::Storage.stride::<f32>(), 4);
+        // A `vec3<f32>` is 12 bytes, aligned to 16 within arrays.
+        assert_eq!(ShaderLayout::Storage.stride::<Vec3>(), 16);
+    }
+
+    #[test]
+    fn elements_bounds() {
+        let command = GpuInsertCommand::<ShaderTypeGpuInsert<Positions>>::elements(
+            GpuBufferId::new(),
+            2..5,
+            (),
+        );
+        let stride = ShaderLayout::Storage.stride::<Vec3>();
+
+        assert_eq!(
+            command.regions[0].bounds,
+            2 * stride..4 * stride + Vec3::SHADER_SIZE.get()
+        );
+
+        let empty = GpuInsertCommand::<ShaderTypeGpuInsert<Positions>>::elements(
+            GpuBufferId::new(),
+            3..3,
+            (),
+        );
+        assert_eq!(empty.regions[0].bounds, 3 * stride..3 * stride);
+    }
+
+    #[test]
+    fn decode() {
+        let mut data = Vec::new();
+        for value in [1.0f32, 2.0, 3.0, 0.0, 4.0, 5.0, 6.0] {
+            data.extend_from_slice(&value.to_ne_bytes());
+        }
+
+        let values = ShaderLayout::Storage.decode::<Vec3>(&data).unwrap();
+        assert_eq!(values, [Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.0, 5.0, 6.0)]);
+
+        let short = &data[..Vec3::SHADER_SIZE.get() as usize - 4];
+        assert!(matches!(
+            ShaderLayout::Storage.decode::<Vec3>(short),
+            Err(GpuInsertError::InvalidLength)
+        ));
+        assert!(matches!(
+            ShaderLayout::Uniform.decode::<f32>(&[]),
+            Err(GpuInsertError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn value_bounds() {
+        let command =
+            GpuInsertCommand::<ShaderTypeGpuInsert<Positions>>::value(GpuBufferId::new(), 32, ());
+
+        assert_eq!(command.regions[0].bounds, 32..32 + Vec3::SHADER_SIZE.get());
+    }
+}
@@ -0,0 +1,321 @@
+use std::{
+    collections::HashMap,
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+};
+
+use bevy::render::{
+    render_resource::{Buffer, BufferAddress, BufferDescriptor, BufferId, BufferUsages},
+    renderer::RenderDevice,
+};
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::{
+    buffer::StagingBuffer, memory::StagingReservation, subscription::SubscriptionReadback,
+    GpuInsertValidationError,
+};
+
+/// Staging buffers owned by the crate in the `RenderWorld`, used by commands without a `staging_buffer` of their own.
+///
+/// The pool grows on demand. A pooled staging buffer returns to the pool once the data staged in it was inserted and it was unmapped.
+/// Free staging buffers are dropped while the pool holds more bytes than the cap of the [`StagingMemory`](crate::StagingMemory).
+pub struct StagingBufferPool {
+    free: Mutex<Vec<PooledStagingBuffer>>,
+    /// Bytes of every staging buffer of the pool, whether it is free or not.
+    allocated: Arc<AtomicU64>,
+    /// Mappings of `staging_buffers` given by commands, which can not be staged in again until they are unmapped.
+    in_flight: Mutex<HashMap<BufferId, Weak<StagingMapping>>>,
+    recycle_sender: Sender<PooledStagingBuffer>,
+    recycle_receiver: Receiver<PooledStagingBuffer>,
+}
+
+struct PooledStagingBuffer {
+    buffer: Buffer,
+    size: BufferAddress,
+}
+
+impl Default for StagingBufferPool {
+    fn default() -> Self {
+        let (recycle_sender, recycle_receiver) = crossbeam_channel::unbounded();
+
+        Self {
+            free: Default::default(),
+            allocated: Default::default(),
+            in_flight: Default::default(),
+            recycle_sender,
+            recycle_receiver,
+        }
+    }
+}
+
+impl StagingBufferPool {
+    /// Bytes of every staging buffer of the pool, whether it is free or not.
+    pub fn allocated(&self) -> BufferAddress {
+        self.allocated.load(Ordering::Acquire)
+    }
+
+    /// Size of the staging buffer [`take`](Self::take) returns for `size` bytes.
+    pub(crate) fn buffer_size(
+        &self,
+        render_device: &RenderDevice,
+        size: BufferAddress,
+    ) -> BufferAddress {
+        let mut free = self.free.lock().unwrap();
+        free.extend(self.recycle_receiver.try_iter());
+
+        match smallest_fitting(&free, size) {
+            Some(index) => free[index].size,
+            None => allocation_size(render_device, size),
+        }
+    }
+
+    /// Drop free staging buffers, largest first, while the pool holds more than `cap` bytes.
+    pub(crate) fn trim(&self, cap: BufferAddress) {
+        let mut free = self.free.lock().unwrap();
+        free.extend(self.recycle_receiver.try_iter());
+        free.sort_unstable_by_key(|pooled| pooled.size);
+
+        while self.allocated() > cap {
+            match free.pop() {
+                Some(pooled) => {
+                    self.allocated.fetch_sub(pooled.size, Ordering::AcqRel);
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Take the smallest free staging buffer holding `size` bytes, or allocate a new one.
+    fn take(&self, render_device: &RenderDevice, size: BufferAddress) -> PooledStagingBuffer {
+        let mut free = self.free.lock().unwrap();
+        free.extend(self.recycle_receiver.try_iter());
+
+        match smallest_fitting(&free, size) {
+            Some(index) => free.swap_remove(index),
+            None => {
+                let size = allocation_size(render_device, size);
+                self.allocated.fetch_add(size, Ordering::AcqRel);
+
+                PooledStagingBuffer {
+                    buffer: render_device.create_buffer(&BufferDescriptor {
+                        label: Some("gpu insert staging buffer"),
+                        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                        size,
+                        mapped_at_creation: false,
+                    }),
+                    size,
+                }
+            }
+        }
+    }
+}
+
+/// Index of the smallest of the `free` staging buffers holding `size` bytes.
+fn smallest_fitting(free: &[PooledStagingBuffer], size: BufferAddress) -> Option<usize> {
+    free.iter()
+        .enumerate()
+        .filter(|(_, pooled)| pooled.size >= size)
+        .min_by_key(|(_, pooled)| pooled.size)
+        .map(|(index, _)| index)
+}
+
+/// Size of a new staging buffer holding `size` bytes, rounded up to reuse it for similar sizes.
+fn allocation_size(render_device: &RenderDevice, size: BufferAddress) -> BufferAddress {
+    size.next_power_of_two()
+        .min(render_device.limits().max_buffer_size)
+}
+
+/// Why data could not be staged by a [`StagingBatch`].
+pub(crate) enum StageError {
+    /// The `staging_buffer` is still mapped for commands of an earlier frame or another [`StagingNode`](crate::StagingNode).
+    /// It is released once their data was inserted, so the command can be staged in a later frame.
+    InUse,
+    Invalid(GpuInsertValidationError),
+}
+
+/// Staging ranges assigned to the commands of one run of a [`StagingNode`](crate::StagingNode).
+///
+/// Commands sharing a `staging_buffer` share one mapping of it, so the buffer is mapped once and unmapped after every command staged in it was released.
+pub(crate) struct StagingBatch<'a> {
+    pool: &'a StagingBufferPool,
+    shared: HashMap<BufferId, (Arc<StagingMapping>, Vec<Range<BufferAddress>>)>,
+}
+
+impl<'a> StagingBatch<'a> {
+    pub(crate) fn new(pool: &'a StagingBufferPool) -> Self {
+        Self {
+            pool,
+            shared: Default::default(),
+        }
+    }
+
+    /// Range of `size` bytes to stage data in, within `staging_buffer` starting at `staging_buffer_offset` if given, otherwise within a pooled staging buffer.
+    pub(crate) fn stage(
+        &mut self,
+        render_device: &RenderDevice,
+        staging_buffer: Option<&StagingBuffer>,
+        staging_buffer_offset: BufferAddress,
+        size: BufferAddress,
+    ) -> Result<StagingRange, StageError> {
+        let staging_buffer = match staging_buffer {
+            Some(staging_buffer) => staging_buffer,
+            None => {
+                let pooled = self.pool.take(render_device, size);
+
+                return Ok(StagingRange {
+                    buffer: pooled.buffer.clone(),
+                    range: 0..size,
+                    mapping: Arc::new(StagingMapping {
+                        buffer: pooled.buffer,
+                        mapped: AtomicBool::new(false),
+                        recycle: Some(Recycle {
+                            sender: self.pool.recycle_sender.clone(),
+                            size: pooled.size,
+                            allocated: self.pool.allocated.clone(),
+                        }),
+                    }),
+                    reservation: None,
+                    readback: None,
+                });
+            }
+        };
+
+        let id = staging_buffer.id();
+
+        if !self.shared.contains_key(&id) {
+            let mut in_flight = self.pool.in_flight.lock().unwrap();
+            in_flight.retain(|_, mapping| mapping.strong_count() > 0);
+
+            if in_flight.contains_key(&id) {
+                return Err(StageError::InUse);
+            }
+
+            let mapping = Arc::new(StagingMapping {
+                buffer: staging_buffer.buffer().clone(),
+                mapped: AtomicBool::new(false),
+                recycle: None,
+            });
+            in_flight.insert(id, Arc::downgrade(&mapping));
+
+            self.shared.insert(id, (mapping, Vec::new()));
+        }
+
+        let (mapping, ranges) = self.shared.get_mut(&id).unwrap();
+        // Validation rejected ranges exceeding the `staging_buffer`, so the end can not overflow.
+        let range = staging_buffer_offset..staging_buffer_offset + size;
+
+        if let Some(overlapped) = ranges
+            .iter()
+            .find(|other| range.start < other.end && other.start < range.end)
+        {
+            return Err(StageError::Invalid(
+                GpuInsertValidationError::OverlappingStaging {
+                    range,
+                    overlapped: overlapped.clone(),
+                },
+            ));
+        }
+
+        ranges.push(range.clone());
+
+        Ok(StagingRange {
+            buffer: staging_buffer.buffer().clone(),
+            range,
+            mapping: mapping.clone(),
+            reservation: None,
+            readback: None,
+        })
+    }
+}
+
+/// Mapping of a staging buffer shared by every [`StagingRange`] within it.
+///
+/// Unmaps the buffer once the last range was released and returns it to the [`StagingBufferPool`] if it is pooled.
+struct StagingMapping {
+    buffer: Buffer,
+    mapped: AtomicBool,
+    recycle: Option<Recycle>,
+}
+
+/// Returns a pooled staging buffer of `size` bytes to its [`StagingBufferPool`].
+struct Recycle {
+    sender: Sender<PooledStagingBuffer>,
+    size: BufferAddress,
+    allocated: Arc<AtomicU64>,
+}
+
+impl Drop for StagingMapping {
+    fn drop(&mut self) {
+        // Buffers which failed to map are neither unmapped nor reused.
+        if !*self.mapped.get_mut() {
+            if let Some(recycle) = self.recycle.take() {
+                recycle.allocated.fetch_sub(recycle.size, Ordering::AcqRel);
+            }
+            return;
+        }
+
+        self.buffer.unmap();
+
+        if let Some(recycle) = self.recycle.take() {
+            // The pool is gone together with the `RenderWorld`, the buffer is simply dropped.
+            let _ = recycle.sender.send(PooledStagingBuffer {
+                buffer: self.buffer.clone(),
+                size: recycle.size,
+            });
+        }
+    }
+}
+
+/// Range of a staging buffer the data of a command is staged in.
+pub struct StagingRange {
+    pub buffer: Buffer,
+    pub range: Range<BufferAddress>,
+    mapping: Arc<StagingMapping>,
+    /// Bytes accounted for in the [`StagingMemory`](crate::StagingMemory) until the range is released.
+    reservation: Option<StagingReservation>,
+    /// Readback of a subscription, which is not issued again until the range is released.
+    readback: Option<SubscriptionReadback>,
+}
+
+impl StagingRange {
+    /// Whether the staging buffer is owned by the [`StagingBufferPool`].
+    pub fn is_pooled(&self) -> bool {
+        self.mapping.recycle.is_some()
+    }
+
+    /// Bytes of staging memory taken up by the range, the whole staging buffer if it is pooled.
+    pub(crate) fn staged_size(&self) -> BufferAddress {
+        match &self.mapping.recycle {
+            Some(recycle) => recycle.size,
+            None => self.range.end - self.range.start,
+        }
+    }
+
+    /// Whether both ranges share one mapping of the same staging buffer.
+    pub(crate) fn shares_mapping(&self, other: &StagingRange) -> bool {
+        Arc::ptr_eq(&self.mapping, &other.mapping)
+    }
+
+    /// Account for the range in the [`StagingMemory`](crate::StagingMemory) until it is released.
+    pub(crate) fn reserve(&mut self, reservation: StagingReservation) {
+        self.reservation = Some(reservation);
+    }
+
+    /// Hold the readback of a subscription until the range is released.
+    pub(crate) fn track(&mut self, readback: SubscriptionReadback) {
+        self.readback = Some(readback);
+    }
+
+    /// Mark the staging buffer as mapped, to be unmapped once every range within it was released.
+    pub(crate) fn set_mapped(&self) {
+        self.mapping.mapped.store(true, Ordering::Release);
+    }
+
+    /// Release the range. Once every range within the staging buffer was released, it is unmapped and returned to the [`StagingBufferPool`] if it is pooled.
+    pub(crate) fn release(self) {
+        drop(self.mapping);
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+
+use bevy::utils::Instant;
+
+use crate::{GpuInsertCommand, GpuInsertInfo};
+
+/// How often a [`GpuReadbackSubscription`] reads its buffers back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadbackCadence {
+    EveryFrame,
+    /// Every given number of frames.
+    EveryFrames(u32),
+    /// Whenever the given duration passed since the last readback.
+    Every(Duration),
+}
+
+/// Subscriptions to read back buffers periodically for `inserts` of `T`, shared by the `MainWorld` and the `RenderWorld`.
+///
+/// The [`StagingNode<T>`](crate::StagingNode) issues the command of every live subscription whose cadence is due.
+pub struct GpuReadbackSubscriptions<T>
+where
+    T: GpuInsertInfo,
+{
+    subscriptions: Arc<Mutex<Vec<Weak<Subscription<T>>>>>,
+}
+
+impl<T> GpuReadbackSubscriptions<T>
+where
+    T: GpuInsertInfo,
+{
+    /// Issue a copy of `command` at the `cadence` until the returned [`GpuReadbackSubscription`] is dropped.
+    ///
+    /// A readback is skipped while the previous one is still in flight or mapped, so a `staging_buffer` given by the `command` is never staged in twice.
+    /// A command rejected by the [`StagingNode<T>`](crate::StagingNode), e.g. because its buffer is not registered yet, is issued again at the `cadence`.
+    /// Only its first failure is reported until a readback was staged again.
+    pub fn subscribe(
+        &self,
+        command: GpuInsertCommand<T>,
+        cadence: ReadbackCadence,
+    ) -> GpuReadbackSubscription<T> {
+        let subscription = Arc::new(Subscription {
+            command,
+            cadence,
+            schedule: Default::default(),
+        });
+
+        self.subscriptions
+            .lock()
+            .unwrap()
+            .push(Arc::downgrade(&subscription));
+
+        GpuReadbackSubscription { subscription }
+    }
+
+    /// Commands of every live subscription due in `frame`, together with the [`SubscriptionReadback`] to hold while they are in flight.
+    pub(crate) fn due(&self, frame: u64) -> Vec<(GpuInsertCommand<T>, SubscriptionReadback)> {
+        let now = Instant::now();
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+
+        // Dropped subscriptions stop.
+        subscriptions.retain(|subscription| subscription.strong_count() > 0);
+
+        subscriptions
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter_map(|subscription| {
+                let mut schedule = subscription.schedule.lock().unwrap();
+
+                if schedule.in_flight.strong_count() > 0 {
+                    return None;
+                }
+
+                let due = match (subscription.cadence, schedule.last) {
+                    (_, None) | (ReadbackCadence::EveryFrame, _) => true,
+                    (ReadbackCadence::EveryFrames(frames), Some((last_frame, _))) => {
+                        frame.wrapping_sub(last_frame) >= frames as u64
+                    }
+                    (ReadbackCadence::Every(duration), Some((_, last_time))) => {
+                        now.duration_since(last_time) >= duration
+                    }
+                };
+
+                if !due {
+                    return None;
+                }
+
+                let readback = SubscriptionReadback {
+                    in_flight: Arc::new(()),
+                    schedule: subscription.schedule.clone(),
+                };
+                schedule.in_flight = Arc::downgrade(&readback.in_flight);
+                schedule.last = Some((frame, now));
+
+                Some((subscription.command.clone(), readback))
+            })
+            .collect()
+    }
+}
+
+impl<T> Default for GpuReadbackSubscriptions<T>
+where
+    T: GpuInsertInfo,
+{
+    fn default() -> Self {
+        Self {
+            subscriptions: Default::default(),
+        }
+    }
+}
+
+impl<T> Clone for GpuReadbackSubscriptions<T>
+where
+    T: GpuInsertInfo,
+{
+    fn clone(&self) -> Self {
+        Self {
+            subscriptions: self.subscriptions.clone(),
+        }
+    }
+}
+
+/// Reads back buffers periodically for `inserts` of `T` until dropped, see [`GpuReadbackSubscriptions::subscribe`].
+pub struct GpuReadbackSubscription<T>
+where
+    T: GpuInsertInfo,
+{
+    subscription: Arc<Subscription<T>>,
+}
+
+impl<T> GpuReadbackSubscription<T>
+where
+    T: GpuInsertInfo,
+{
+    pub fn command(&self) -> &GpuInsertCommand<T> {
+        &self.subscription.command
+    }
+
+    pub fn cadence(&self) -> ReadbackCadence {
+        self.subscription.cadence
+    }
+}
+
+struct Subscription<T>
+where
+    T: GpuInsertInfo,
+{
+    command: GpuInsertCommand<T>,
+    cadence: ReadbackCadence,
+    schedule: Arc<Mutex<Schedule>>,
+}
+
+#[derive(Default)]
+struct Schedule {
+    /// Frame and time of the last readback.
+    last: Option<(u64, Instant)>,
+    in_flight: Weak<()>,
+    /// Whether a readback failed since the last one was staged.
+    failed: bool,
+}
+
+/// Held by a readback of a subscription until its staging range was released.
+pub(crate) struct SubscriptionReadback {
+    in_flight: Arc<()>,
+    schedule: Arc<Mutex<Schedule>>,
+}
+
+impl SubscriptionReadback {
+    /// Record that the readback was staged, so the next failure is reported again.
+    pub(crate) fn staged(&self) {
+        self.schedule.lock().unwrap().failed = false;
+    }
+
+    /// Record that the readback failed. Returns whether to report it, which is only the case for the first failure since a readback was staged.
+    pub(crate) fn failed(&self) -> bool {
+        !std::mem::replace(&mut self.schedule.lock().unwrap().failed, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{GpuReadbackSubscriptions, ReadbackCadence};
+    use crate::{GpuBufferId, GpuDataReady, GpuInsertCommand};
+
+    type Subscriptions = GpuReadbackSubscriptions<GpuDataReady<u32>>;
+
+    fn command() -> GpuInsertCommand<GpuDataReady<u32>> {
+        GpuInsertCommand::new(GpuBufferId::new(), 0..4, 0)
+    }
+
+    #[test]
+    fn every_frames_skips_frames() {
+        let subscriptions = Subscriptions::default();
+        let _subscription = subscriptions.subscribe(command(), ReadbackCadence::EveryFrames(3));
+
+        let issued: Vec<_> = (1..=7)
+            .filter(|&frame| !subscriptions.due(frame).is_empty())
+            .collect();
+        assert_eq!(issued, [1, 4, 7]);
+    }
+
+    #[test]
+    fn every_duration_waits() {
+        let subscriptions = Subscriptions::default();
+        let _subscription =
+            subscriptions.subscribe(command(), ReadbackCadence::Every(Duration::from_secs(3600)));
+
+        assert_eq!(subscriptions.due(1).len(), 1);
+        assert!(subscriptions.due(2).is_empty());
+    }
+
+    #[test]
+    fn skipped_while_in_flight() {
+        let subscriptions = Subscriptions::default();
+        let _subscription = subscriptions.subscribe(command(), ReadbackCadence::EveryFrame);
+
+        let mut due = subscriptions.due(1);
+        assert_eq!(due.len(), 1);
+        let (_, readback) = due.pop().unwrap();
+
+        assert!(subscriptions.due(2).is_empty());
+
+        drop(readback);
+        assert_eq!(subscriptions.due(3).len(), 1);
+    }
+
+    #[test]
+    fn first_failure_is_reported_until_staged() {
+        let subscriptions = Subscriptions::default();
+        let _subscription = subscriptions.subscribe(command(), ReadbackCadence::EveryFrame);
+
+        let (_, readback) = subscriptions.due(1).pop().unwrap();
+        assert!(readback.failed());
+        drop(readback);
+
+        let (_, readback) = subscriptions.due(2).pop().unwrap();
+        assert!(!readback.failed());
+        drop(readback);
+
+        let (_, readback) = subscriptions.due(3).pop().unwrap();
+        readback.staged();
+        drop(readback);
+
+        let (_, readback) = subscriptions.due(4).pop().unwrap();
+        assert!(readback.failed());
+    }
+
+    #[test]
+    fn dropped_subscription_stops() {
+        let subscriptions = Subscriptions::default();
+        let subscription = subscriptions.subscribe(command(), ReadbackCadence::EveryFrame);
+        let _other = subscriptions.subscribe(command(), ReadbackCadence::EveryFrame);
+
+        assert_eq!(subscriptions.due(1).len(), 2);
+
+        drop(subscription);
+        assert_eq!(subscriptions.due(2).len(), 1);
+        assert_eq!(subscriptions.subscriptions.lock().unwrap().len(), 1);
+    }
+}
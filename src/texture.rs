@@ -0,0 +1,206 @@
+use std::borrow::Cow;
+
+use bevy::render::render_resource::{BufferAddress, Extent3d, Origin3d, Texture, TextureFormat};
+
+use crate::{buffer::StagingBuffer, GpuInsertInfo};
+
+/// Required alignment of the `bytes_per_row` when copying a texture to a buffer, see `wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`.
+const COPY_BYTES_PER_ROW_ALIGNMENT: u32 = 256;
+
+/// Issue an `insert` with data from `texture` copied to a staging buffer to be staged (readable) for the `MainWorld`.
+///
+/// Texels of the `mip_level` within the `extent` starting at the `origin` are copied to the staging buffer.
+/// The `z` of the `origin` and the `depth_or_array_layers` of the `extent` select the array layers of 2D textures or the depth slices of 3D textures.
+/// The staging buffer is taken from the [`StagingBufferPool`](crate::StagingBufferPool) unless a `staging_buffer` is given, then the data is copied to it starting at the `staging_buffer_offset`.
+/// Rows are padded on the Gpu, reserve [`GpuTextureInsertCommand::staging_size`] bytes in a given `staging_buffer`.
+/// The `insert` receives tightly packed rows, see [`GpuInsertData::texture`](crate::GpuInsertData::texture).
+///
+/// Dispatched by pushing a [`GpuTextureInsertCommand`] to the resource [`Vec<GpuTextureInsertCommand<T>>`] in the `RenderWorld`, staged by the same [`StagingNode<T>`](crate::StagingNode) as [`GpuInsertCommands<T>`](crate::GpuInsertCommand).
+pub struct GpuTextureInsertCommand<T>
+where
+    T: GpuInsertInfo,
+{
+    pub texture: Texture,
+    /// Format of the `texture`, which can not be queried from it.
+    pub format: TextureFormat,
+    pub mip_level: u32,
+    pub origin: Origin3d,
+    pub extent: Extent3d,
+    pub staging_buffer: Option<StagingBuffer>,
+    pub staging_buffer_offset: BufferAddress,
+    pub info: T::Info,
+}
+
+impl<T> GpuTextureInsertCommand<T>
+where
+    T: GpuInsertInfo,
+{
+    /// Layout of the tightly packed rows passed to the `insert`.
+    pub fn layout(&self) -> GpuTextureLayout {
+        GpuTextureLayout::new(self.format, self.mip_level, self.extent)
+    }
+
+    /// Size of the padded rows copied to the staging buffer.
+    pub fn staging_size(&self) -> BufferAddress {
+        let layout = self.layout();
+
+        layout.padded_bytes_per_row() as BufferAddress
+            * layout.rows_per_image as BufferAddress
+            * layout.size.depth_or_array_layers as BufferAddress
+    }
+}
+
+impl<T> Clone for GpuTextureInsertCommand<T>
+where
+    T: GpuInsertInfo,
+{
+    fn clone(&self) -> Self {
+        Self {
+            texture: self.texture.clone(),
+            format: self.format,
+            mip_level: self.mip_level,
+            origin: self.origin,
+            extent: self.extent,
+            staging_buffer: self.staging_buffer.clone(),
+            staging_buffer_offset: self.staging_buffer_offset,
+            info: self.info.clone(),
+        }
+    }
+}
+
+/// Layout of the tightly packed rows of texels staged by a [`GpuTextureInsertCommand`].
+#[derive(Clone, Copy, Debug)]
+pub struct GpuTextureLayout {
+    pub format: TextureFormat,
+    pub mip_level: u32,
+    /// Texels copied from the mip level, the `depth_or_array_layers` are stored one after another.
+    pub size: Extent3d,
+    /// Bytes of a row of texel blocks.
+    pub bytes_per_row: u32,
+    /// Rows of texel blocks of every array layer or depth slice.
+    pub rows_per_image: u32,
+}
+
+impl GpuTextureLayout {
+    /// Layout of the texels of `format` within `size` copied from the `mip_level`.
+    fn new(format: TextureFormat, mip_level: u32, size: Extent3d) -> Self {
+        let format_info = format.describe();
+        let (block_width, block_height) = format_info.block_dimensions;
+
+        let blocks_per_row = size.width.div_ceil(block_width as u32);
+
+        Self {
+            format,
+            mip_level,
+            size,
+            bytes_per_row: blocks_per_row * format_info.block_size as u32,
+            rows_per_image: size.height.div_ceil(block_height as u32),
+        }
+    }
+
+    /// Bytes of a row of texel blocks as copied to the staging buffer.
+    pub fn padded_bytes_per_row(&self) -> u32 {
+        self.bytes_per_row
+            .next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT)
+    }
+
+    /// Strip the padding of every row of `data` copied to the staging buffer.
+    pub(crate) fn pack<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        let padded_bytes_per_row = self.padded_bytes_per_row() as usize;
+        let bytes_per_row = self.bytes_per_row as usize;
+
+        if padded_bytes_per_row == bytes_per_row {
+            return Cow::Borrowed(data);
+        }
+
+        let mut packed = Vec::with_capacity(data.len() / padded_bytes_per_row * bytes_per_row);
+        for row in data.chunks_exact(padded_bytes_per_row) {
+            packed.extend_from_slice(&row[..bytes_per_row]);
+        }
+
+        Cow::Owned(packed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use bevy::render::render_resource::{Extent3d, TextureFormat};
+
+    use super::GpuTextureLayout;
+
+    /// Rows as copied to the staging buffer, every byte holding the index of its row and padding set to `0xff`.
+    fn padded_rows(layout: &GpuTextureLayout, rows: usize) -> Vec<u8> {
+        let padded_bytes_per_row = layout.padded_bytes_per_row() as usize;
+
+        (0..rows)
+            .flat_map(|row| {
+                (0..padded_bytes_per_row).map(move |byte| {
+                    if byte < layout.bytes_per_row as usize {
+                        row as u8
+                    } else {
+                        0xff
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn packed_rows(layout: &GpuTextureLayout, rows: usize) -> Vec<u8> {
+        (0..rows)
+            .flat_map(|row| std::iter::repeat_n(row as u8, layout.bytes_per_row as usize))
+            .collect()
+    }
+
+    #[test]
+    fn pack_strips_padding_of_every_layer() {
+        let size = Extent3d {
+            width: 10,
+            height: 3,
+            depth_or_array_layers: 2,
+        };
+        let layout = GpuTextureLayout::new(TextureFormat::Rgba8Unorm, 0, size);
+
+        assert_eq!(layout.bytes_per_row, 40);
+        assert_eq!(layout.padded_bytes_per_row(), 256);
+        assert_eq!(layout.rows_per_image, 3);
+
+        let rows = (layout.rows_per_image * size.depth_or_array_layers) as usize;
+        let data = padded_rows(&layout, rows);
+        assert_eq!(layout.pack(&data), packed_rows(&layout, rows));
+    }
+
+    #[test]
+    fn pack_strips_padding_of_texel_blocks() {
+        let size = Extent3d {
+            width: 18,
+            height: 10,
+            depth_or_array_layers: 1,
+        };
+        let layout = GpuTextureLayout::new(TextureFormat::Bc1RgbaUnorm, 0, size);
+
+        // 5 blocks of 4x4 texels and 8 bytes per row, 3 rows of blocks.
+        assert_eq!(layout.bytes_per_row, 40);
+        assert_eq!(layout.padded_bytes_per_row(), 256);
+        assert_eq!(layout.rows_per_image, 3);
+
+        let data = padded_rows(&layout, 3);
+        assert_eq!(layout.pack(&data), packed_rows(&layout, 3));
+    }
+
+    #[test]
+    fn pack_borrows_aligned_rows() {
+        let size = Extent3d {
+            width: 64,
+            height: 2,
+            depth_or_array_layers: 1,
+        };
+        let layout = GpuTextureLayout::new(TextureFormat::Rgba8Unorm, 0, size);
+
+        assert_eq!(layout.padded_bytes_per_row(), 256);
+
+        let data = padded_rows(&layout, 2);
+        assert!(matches!(layout.pack(&data), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,332 @@
+use std::{fmt, ops::Range};
+
+use bevy::render::render_resource::{BufferAddress, BufferUsages, WgpuLimits};
+
+use crate::{
+    buffer::StagingBuffer,
+    registry::{GpuBufferId, GpuBufferRegistry},
+    GpuInsertCommand, GpuInsertInfo, GpuTextureInsertCommand,
+};
+
+/// Required alignment of copied ranges, see `wgpu::COPY_BUFFER_ALIGNMENT`.
+const COPY_BUFFER_ALIGNMENT: BufferAddress = 4;
+/// Required alignment of the start of mapped ranges, see `wgpu::MAP_ALIGNMENT`.
+const MAP_ALIGNMENT: BufferAddress = 8;
+
+/// Why a command was rejected by the [`StagingNode`](crate::StagingNode) before anything was copied.
+#[derive(Clone, Debug)]
+pub enum GpuInsertValidationError {
+    /// The command has no data to copy.
+    Empty,
+    /// The `bounds` of the region at `region` start after they end.
+    InvertedBounds {
+        region: usize,
+        bounds: Range<BufferAddress>,
+    },
+    /// The `bounds` of the region at `region` do not start or end at a multiple of `COPY_BUFFER_ALIGNMENT`.
+    UnalignedBounds {
+        region: usize,
+        bounds: Range<BufferAddress>,
+    },
+    /// The `bounds` of the region at `region` exceed the `size` of its buffer.
+    BoundsOutOfRange {
+        region: usize,
+        bounds: Range<BufferAddress>,
+        size: BufferAddress,
+    },
+    /// The buffer of the region at `region` was not created with [`BufferUsages::COPY_SRC`].
+    MissingCopySrc { region: usize },
+    /// The `staging_buffer_offset` is not a multiple of `MAP_ALIGNMENT` or the size of a texel block.
+    UnalignedStagingOffset { offset: BufferAddress },
+    /// The staged data within `range` exceeds the `size` of the `staging_buffer`.
+    StagingOutOfRange {
+        range: Range<BufferAddress>,
+        size: BufferAddress,
+    },
+    /// The `staging_buffer` was not created with [`BufferUsages::MAP_READ`] and [`BufferUsages::COPY_DST`].
+    MissingStagingUsages { usage: BufferUsages },
+    /// The staged data of `size` bytes exceeds the `max_buffer_size` of the `RenderDevice`.
+    ExceedsMaxBufferSize {
+        size: BufferAddress,
+        max: BufferAddress,
+    },
+    /// The `origin` or `extent` of a texture is not a multiple of the texel block dimensions of its format.
+    UnalignedTexelBlocks,
+    /// The staged data within `range` overlaps the `overlapped` range of another command staged in the same `staging_buffer`.
+    OverlappingStaging {
+        range: Range<BufferAddress>,
+        overlapped: Range<BufferAddress>,
+    },
+    /// No buffer is registered as the given id in the [`GpuBufferRegistry`](crate::GpuBufferRegistry).
+    MissingBuffer(GpuBufferId),
+}
+
+impl fmt::Display for GpuInsertValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuInsertValidationError::Empty => write!(f, "the command has no data to copy"),
+            GpuInsertValidationError::InvertedBounds { region, bounds } => write!(
+                f,
+                "the bounds {:?} of region {} start after they end",
+                bounds, region
+            ),
+            GpuInsertValidationError::UnalignedBounds { region, bounds } => write!(
+                f,
+                "the bounds {:?} of region {} are not aligned to {} bytes",
+                bounds, region, COPY_BUFFER_ALIGNMENT
+            ),
+            GpuInsertValidationError::BoundsOutOfRange {
+                region,
+                bounds,
+                size,
+            } => write!(
+                f,
+                "the bounds {:?} of region {} exceed the buffer of {} bytes",
+                bounds, region, size
+            ),
+            GpuInsertValidationError::MissingCopySrc { region } => write!(
+                f,
+                "the buffer of region {} is missing `BufferUsages::COPY_SRC`",
+                region
+            ),
+            GpuInsertValidationError::UnalignedStagingOffset { offset } => write!(
+                f,
+                "the staging buffer offset {} is not aligned to {} bytes or the texel block size",
+                offset, MAP_ALIGNMENT
+            ),
+            GpuInsertValidationError::StagingOutOfRange { range, size } => write!(
+                f,
+                "the staged range {:?} exceeds the staging buffer of {} bytes",
+                range, size
+            ),
+            GpuInsertValidationError::MissingStagingUsages { usage } => write!(
+                f,
+                "the staging buffer has usages {:?} but requires `MAP_READ | COPY_DST`",
+                usage
+            ),
+            GpuInsertValidationError::ExceedsMaxBufferSize { size, max } => write!(
+                f,
+                "the staged data of {} bytes exceeds the max buffer size of {} bytes",
+                size, max
+            ),
+            GpuInsertValidationError::UnalignedTexelBlocks => write!(
+                f,
+                "the origin or extent is not aligned to the texel blocks of the format"
+            ),
+            GpuInsertValidationError::OverlappingStaging { range, overlapped } => write!(
+                f,
+                "the staged range {:?} overlaps the range {:?} of another command",
+                range, overlapped
+            ),
+            GpuInsertValidationError::MissingBuffer(id) => {
+                write!(f, "no buffer is registered as {:?}", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GpuInsertValidationError {}
+
+/// Validate the regions and staging of `command` before anything is copied.
+pub(crate) fn validate_gpu_insert_command<T>(
+    command: &GpuInsertCommand<T>,
+    limits: &WgpuLimits,
+    registry: &GpuBufferRegistry,
+) -> Result<(), GpuInsertValidationError>
+where
+    T: GpuInsertInfo,
+{
+    for (index, region) in command.regions.iter().enumerate() {
+        let buffer = region.buffer.resolve(registry)?;
+        validate_region(index, &region.bounds, buffer.size(), buffer.usage())?;
+    }
+
+    validate_staging(
+        command.staging_buffer.as_ref(),
+        command.staging_buffer_offset,
+        command.size(),
+        MAP_ALIGNMENT,
+        limits,
+    )
+}
+
+/// Validate the `bounds` of the region at `index` within a buffer of `size` bytes created with `usage`.
+fn validate_region(
+    index: usize,
+    bounds: &Range<BufferAddress>,
+    size: BufferAddress,
+    usage: BufferUsages,
+) -> Result<(), GpuInsertValidationError> {
+    if bounds.start > bounds.end {
+        return Err(GpuInsertValidationError::InvertedBounds {
+            region: index,
+            bounds: bounds.clone(),
+        });
+    }
+
+    if !bounds.start.is_multiple_of(COPY_BUFFER_ALIGNMENT)
+        || !bounds.end.is_multiple_of(COPY_BUFFER_ALIGNMENT)
+    {
+        return Err(GpuInsertValidationError::UnalignedBounds {
+            region: index,
+            bounds: bounds.clone(),
+        });
+    }
+
+    if bounds.end > size {
+        return Err(GpuInsertValidationError::BoundsOutOfRange {
+            region: index,
+            bounds: bounds.clone(),
+            size,
+        });
+    }
+
+    if !usage.contains(BufferUsages::COPY_SRC) {
+        return Err(GpuInsertValidationError::MissingCopySrc { region: index });
+    }
+
+    Ok(())
+}
+
+/// Validate the texel blocks and staging of `command` before anything is copied.
+pub(crate) fn validate_gpu_texture_insert_command<T>(
+    command: &GpuTextureInsertCommand<T>,
+    limits: &WgpuLimits,
+) -> Result<(), GpuInsertValidationError>
+where
+    T: GpuInsertInfo,
+{
+    let format_info = command.format.describe();
+    let (block_width, block_height) = format_info.block_dimensions;
+    let (block_width, block_height) = (block_width as u32, block_height as u32);
+
+    if !command.origin.x.is_multiple_of(block_width)
+        || !command.origin.y.is_multiple_of(block_height)
+        || !command.extent.width.is_multiple_of(block_width)
+        || !command.extent.height.is_multiple_of(block_height)
+    {
+        return Err(GpuInsertValidationError::UnalignedTexelBlocks);
+    }
+
+    let alignment = (format_info.block_size as BufferAddress).max(MAP_ALIGNMENT);
+
+    validate_staging(
+        command.staging_buffer.as_ref(),
+        command.staging_buffer_offset,
+        command.staging_size(),
+        alignment,
+        limits,
+    )
+}
+
+fn validate_staging(
+    staging_buffer: Option<&StagingBuffer>,
+    staging_buffer_offset: BufferAddress,
+    size: BufferAddress,
+    alignment: BufferAddress,
+    limits: &WgpuLimits,
+) -> Result<(), GpuInsertValidationError> {
+    if size == 0 {
+        return Err(GpuInsertValidationError::Empty);
+    }
+
+    if size > limits.max_buffer_size {
+        return Err(GpuInsertValidationError::ExceedsMaxBufferSize {
+            size,
+            max: limits.max_buffer_size,
+        });
+    }
+
+    let staging_buffer = match staging_buffer {
+        Some(staging_buffer) => staging_buffer,
+        // Pooled staging buffers are sized and aligned by the pool.
+        None => return Ok(()),
+    };
+
+    if !staging_buffer
+        .usage()
+        .contains(BufferUsages::MAP_READ | BufferUsages::COPY_DST)
+    {
+        return Err(GpuInsertValidationError::MissingStagingUsages {
+            usage: staging_buffer.usage(),
+        });
+    }
+
+    if !staging_buffer_offset.is_multiple_of(alignment) {
+        return Err(GpuInsertValidationError::UnalignedStagingOffset {
+            offset: staging_buffer_offset,
+        });
+    }
+
+    staging_range(staging_buffer_offset, size, staging_buffer.size())?;
+
+    Ok(())
+}
+
+/// Range of `size` bytes starting at the `offset` within a staging buffer of `staging_size` bytes.
+fn staging_range(
+    offset: BufferAddress,
+    size: BufferAddress,
+    staging_size: BufferAddress,
+) -> Result<Range<BufferAddress>, GpuInsertValidationError> {
+    match offset.checked_add(size) {
+        Some(end) if end <= staging_size => Ok(offset..end),
+        // The end of a range overflowing the address space is reported as the largest address.
+        end => Err(GpuInsertValidationError::StagingOutOfRange {
+            range: offset..end.unwrap_or(BufferAddress::MAX),
+            size: staging_size,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::render_resource::{BufferAddress, BufferUsages};
+
+    use super::{staging_range, validate_region, GpuInsertValidationError};
+
+    const USAGE: BufferUsages = BufferUsages::COPY_SRC;
+
+    #[test]
+    fn inverted_bounds() {
+        assert!(matches!(
+            validate_region(1, &(8..4), 16, USAGE),
+            Err(GpuInsertValidationError::InvertedBounds { region: 1, bounds }) if bounds == (8..4)
+        ));
+        // Inverted bounds are reported before unaligned ones.
+        assert!(matches!(
+            validate_region(0, &(9..3), 16, USAGE),
+            Err(GpuInsertValidationError::InvertedBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn unaligned_bounds() {
+        assert!(matches!(
+            validate_region(2, &(2..8), 16, USAGE),
+            Err(GpuInsertValidationError::UnalignedBounds { region: 2, bounds }) if bounds == (2..8)
+        ));
+        assert!(matches!(
+            validate_region(0, &(4..10), 16, USAGE),
+            Err(GpuInsertValidationError::UnalignedBounds { .. })
+        ));
+        assert!(validate_region(0, &(4..8), 16, USAGE).is_ok());
+        assert!(validate_region(0, &(8..8), 16, USAGE).is_ok());
+    }
+
+    #[test]
+    fn staging_range_overflow() {
+        let offset = BufferAddress::MAX - 7;
+
+        assert!(matches!(
+            staging_range(offset, 16, 64),
+            Err(GpuInsertValidationError::StagingOutOfRange { range, size: 64 })
+                if range == (offset..BufferAddress::MAX)
+        ));
+        assert!(matches!(
+            staging_range(56, 16, 64),
+            Err(GpuInsertValidationError::StagingOutOfRange { .. })
+        ));
+        assert_eq!(staging_range(48, 16, 64).unwrap(), 48..64);
+    }
+}